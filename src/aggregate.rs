@@ -0,0 +1,105 @@
+use std::collections::BTreeMap;
+
+use crate::{Field, Uplink};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stats {
+    pub count: usize,
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    pub last: f64,
+}
+
+/// Statistics of one field over the window starting at `start_s` (seconds since the Unix epoch).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Aggregate {
+    pub start_s: u64,
+    pub field: Field,
+    pub stats: Stats,
+}
+
+struct Accumulator {
+    count: usize,
+    min: f64,
+    max: f64,
+    sum: f64,
+    last_s: u64,
+    last: f64,
+}
+
+/// Aggregates every field over windows of `window_s` seconds, aligned to the Unix epoch.
+///
+/// The output is ordered by window, then by field. Windows and fields without readings are omitted.
+/// A `window_s` of zero yields no windows.
+pub fn aggregate(readings: &[(u64, Uplink)], window_s: u64) -> Vec<Aggregate> {
+    if window_s == 0 {
+        return Vec::new();
+    }
+
+    let mut accumulators = BTreeMap::<(u64, Field), Accumulator>::new();
+
+    for (timestamp_s, uplink) in readings {
+        let start_s = timestamp_s - timestamp_s % window_s;
+        for field in Field::ALL {
+            if let Some(value) = uplink.value_f64(*field) {
+                accumulators
+                    .entry((start_s, *field))
+                    .and_modify(|a| a.add(*timestamp_s, value))
+                    .or_insert(Accumulator {
+                        count: 1,
+                        min: value,
+                        max: value,
+                        sum: value,
+                        last_s: *timestamp_s,
+                        last: value,
+                    });
+            }
+        }
+    }
+
+    accumulators
+        .into_iter()
+        .map(|((start_s, field), a)| Aggregate {
+            start_s,
+            field,
+            stats: Stats {
+                count: a.count,
+                min: a.min,
+                max: a.max,
+                mean: a.sum / a.count as f64,
+                last: a.last,
+            },
+        })
+        .collect()
+}
+
+/// The `q`-quantile (0 to 1) of `values` by nearest rank.
+pub fn quantile(values: &[f32], q: f32) -> Option<f32> {
+    if values.is_empty() {
+        return None;
+    }
+
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f32::total_cmp);
+    let rank = (q.clamp(0.0, 1.0) * (sorted.len() - 1) as f32).round() as usize;
+    Some(sorted[rank])
+}
+
+impl Accumulator {
+    fn add(&mut self, timestamp_s: u64, value: f64) {
+        self.count += 1;
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        self.sum += value;
+        if timestamp_s >= self.last_s {
+            self.last_s = timestamp_s;
+            self.last = value;
+        }
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[path = "./test_aggregate.rs"]
+mod test_aggregate;
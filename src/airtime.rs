@@ -0,0 +1,159 @@
+use std::collections::VecDeque;
+use std::io::{Error, ErrorKind, Result};
+use std::ops::RangeInclusive;
+
+/// Bytes a LoRaWAN data frame adds around the application payload: MHDR, FHDR without options,
+/// FPort and MIC.
+pub const LORAWAN_OVERHEAD: usize = 1 + 7 + 1 + 4;
+
+/// The spreading factors of LoRa modulation.
+pub const SPREADING_FACTORS: RangeInclusive<u8> = 5..=12;
+
+/// LoRa modulation parameters of a transmission.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Modulation {
+    pub spreading_factor: u8,
+    pub bandwidth_hz: u32,
+    /// 1 to 4, for coding rates 4/5 to 4/8.
+    pub coding_rate: u8,
+    pub preamble_symbols: u16,
+    pub explicit_header: bool,
+    pub crc: bool,
+}
+
+impl Modulation {
+    /// The modulation LoRaWAN uses for uplinks: coding rate 4/5, an 8 symbol preamble, an explicit
+    /// header and a CRC.
+    pub fn lorawan(spreading_factor: u8, bandwidth_hz: u32) -> Self {
+        Self {
+            spreading_factor,
+            bandwidth_hz,
+            coding_rate: 1,
+            preamble_symbols: 8,
+            explicit_header: true,
+            crc: true,
+        }
+    }
+
+    /// Chips per symbol, if the spreading factor is valid.
+    fn chips(&self) -> Option<u64> {
+        SPREADING_FACTORS
+            .contains(&self.spreading_factor)
+            .then(|| 1 << self.spreading_factor)
+    }
+
+    /// Low data rate optimisation is mandated when a symbol lasts longer than 16 ms.
+    fn low_data_rate_optimize(&self) -> bool {
+        self.chips()
+            .is_some_and(|chips| chips * 1000 > 16 * self.bandwidth_hz as u64)
+    }
+}
+
+/// Time on air of a PHY payload of `length` bytes, in microseconds, following the formula of the
+/// Semtech SX1276 datasheet. `None` if the spreading factor is not in `SPREADING_FACTORS`.
+pub fn airtime_us(modulation: &Modulation, length: usize) -> Option<u64> {
+    let sf = modulation.spreading_factor as f64;
+    let symbol_us = modulation.chips()? as f64 * 1e6 / modulation.bandwidth_hz as f64;
+    let preamble_us = (modulation.preamble_symbols as f64 + 4.25) * symbol_us;
+
+    let crc = if modulation.crc { 16.0 } else { 0.0 };
+    let implicit_header = if modulation.explicit_header {
+        0.0
+    } else {
+        20.0
+    };
+    let de = if modulation.low_data_rate_optimize() {
+        2.0
+    } else {
+        0.0
+    };
+    let numerator = 8.0 * length as f64 - 4.0 * sf + 28.0 + crc - implicit_header;
+    let blocks = (numerator / (4.0 * (sf - de))).ceil().max(0.0);
+    let payload_symbols = 8.0 + blocks * (modulation.coding_rate as f64 + 4.0);
+
+    Some((preamble_us + payload_symbols * symbol_us).ceil() as u64)
+}
+
+/// Time on air of a LoRaWAN uplink carrying `length` bytes of application payload, in microseconds.
+pub fn uplink_airtime_us(modulation: &Modulation, length: usize) -> Option<u64> {
+    airtime_us(modulation, length + LORAWAN_OVERHEAD)
+}
+
+/// A range of frequencies sharing one duty-cycle limit.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SubBand {
+    pub min_hz: u32,
+    pub max_hz: u32,
+    /// The fraction of time a transmitter may be on air.
+    pub duty_cycle: f64,
+}
+
+/// Accounts the airtime of one transmitter per sub-band over a sliding window, usually an hour.
+/// Transmissions are recorded in chronological order.
+#[derive(Clone, Debug)]
+pub struct DutyCycle {
+    sub_bands: Vec<SubBand>,
+    window_s: u64,
+    transmissions: Vec<VecDeque<(u64, u64)>>,
+}
+
+impl DutyCycle {
+    pub fn new(sub_bands: &[SubBand], window_s: u64) -> Self {
+        Self {
+            sub_bands: sub_bands.to_vec(),
+            window_s,
+            transmissions: vec![VecDeque::new(); sub_bands.len()],
+        }
+    }
+
+    pub fn record(&mut self, timestamp_s: u64, frequency_hz: u32, airtime_us: u64) -> Result<()> {
+        let band = self.sub_band(frequency_hz).ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                format!("{} Hz is not in any sub-band", frequency_hz),
+            )
+        })?;
+        self.transmissions[band].push_back((timestamp_s, airtime_us));
+        Ok(())
+    }
+
+    /// Airtime spent in the window ending at `now_s` in the sub-band of `frequency_hz`.
+    pub fn used_us(&mut self, now_s: u64, frequency_hz: u32) -> Option<u64> {
+        let band = self.sub_band(frequency_hz)?;
+        let transmissions = &mut self.transmissions[band];
+        while transmissions
+            .front()
+            .is_some_and(|(timestamp_s, _)| now_s.saturating_sub(*timestamp_s) >= self.window_s)
+        {
+            transmissions.pop_front();
+        }
+        Some(transmissions.iter().map(|(_, airtime_us)| airtime_us).sum())
+    }
+
+    /// Airtime still available in the window ending at `now_s` in the sub-band of `frequency_hz`.
+    pub fn remaining_us(&mut self, now_s: u64, frequency_hz: u32) -> Option<u64> {
+        let used_us = self.used_us(now_s, frequency_hz)?;
+        let band = self.sub_band(frequency_hz)?;
+        let allowed_us =
+            (self.window_s as f64 * 1e6 * self.sub_bands[band].duty_cycle).round() as u64;
+        Some(allowed_us.saturating_sub(used_us))
+    }
+
+    /// Whether a transmission of `airtime_us` at `now_s` stays within the limit. Frequencies
+    /// outside every sub-band are never allowed.
+    pub fn allows(&mut self, now_s: u64, frequency_hz: u32, airtime_us: u64) -> bool {
+        self.remaining_us(now_s, frequency_hz)
+            .is_some_and(|remaining_us| airtime_us <= remaining_us)
+    }
+
+    fn sub_band(&self, frequency_hz: u32) -> Option<usize> {
+        self.sub_bands
+            .iter()
+            .position(|band| (band.min_hz..band.max_hz).contains(&frequency_hz))
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[path = "./test_airtime.rs"]
+mod test_airtime;
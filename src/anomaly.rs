@@ -0,0 +1,127 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::{Field, Uplink};
+
+/// Per-field detection thresholds. A `None` threshold disables that check.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Thresholds {
+    /// Number of previous readings the z-score is computed against.
+    pub window: usize,
+    pub max_z_score: Option<f32>,
+    /// Maximum absolute change per hour, in the units of the field's accessor.
+    pub max_rate_per_hour: Option<f32>,
+    /// Maximum number of consecutive identical readings.
+    pub max_repeats: Option<usize>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Kind {
+    ZScore(f32),
+    RateOfChange(f32),
+    Stuck(usize),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Anomaly {
+    pub timestamp_s: u64,
+    pub field: Field,
+    pub value: f32,
+    pub kind: Kind,
+}
+
+/// Detects anomalous readings of one device. Uplinks must be pushed in chronological order.
+#[derive(Clone, Debug, Default)]
+pub struct Detector {
+    thresholds: HashMap<Field, Thresholds>,
+    history: HashMap<Field, VecDeque<(u64, f32)>>,
+    repeats: HashMap<Field, usize>,
+}
+
+impl Detector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_thresholds(&mut self, field: Field, thresholds: Thresholds) {
+        self.thresholds.insert(field, thresholds);
+    }
+
+    pub fn push(&mut self, timestamp_s: u64, uplink: &Uplink) -> Vec<Anomaly> {
+        let mut output = Vec::new();
+
+        for (field, thresholds) in &self.thresholds {
+            let value = match uplink.value(*field) {
+                Some(value) => value,
+                None => continue,
+            };
+            let history = self.history.entry(*field).or_default();
+            let repeats = self.repeats.entry(*field).or_default();
+            let mut anomaly = |kind| {
+                output.push(Anomaly {
+                    timestamp_s,
+                    field: *field,
+                    value,
+                    kind,
+                })
+            };
+
+            if let (Some(max_z_score), Some(z_score)) =
+                (thresholds.max_z_score, z_score(history, value))
+            {
+                if z_score.abs() > max_z_score {
+                    anomaly(Kind::ZScore(z_score));
+                }
+            }
+
+            if let (Some(max_rate), Some(&(previous_s, previous))) =
+                (thresholds.max_rate_per_hour, history.back())
+            {
+                if timestamp_s > previous_s {
+                    let rate = (value - previous) * 3600.0 / (timestamp_s - previous_s) as f32;
+                    if rate.abs() > max_rate {
+                        anomaly(Kind::RateOfChange(rate));
+                    }
+                }
+            }
+
+            *repeats = match history.back() {
+                Some((_, previous)) if *previous == value => *repeats + 1,
+                _ => 1,
+            };
+            if let Some(max_repeats) = thresholds.max_repeats {
+                if *repeats == max_repeats + 1 {
+                    anomaly(Kind::Stuck(*repeats));
+                }
+            }
+
+            history.push_back((timestamp_s, value));
+            while history.len() > thresholds.window.max(1) {
+                history.pop_front();
+            }
+        }
+
+        output.sort_by_key(|a| a.field);
+        output
+    }
+}
+
+fn z_score(history: &VecDeque<(u64, f32)>, value: f32) -> Option<f32> {
+    if history.len() < 2 {
+        return None;
+    }
+
+    let n = history.len() as f32;
+    let mean = history.iter().map(|(_, v)| v).sum::<f32>() / n;
+    let variance = history.iter().map(|(_, v)| (v - mean).powi(2)).sum::<f32>() / (n - 1.0);
+
+    if variance > 0.0 {
+        Some((value - mean) / variance.sqrt())
+    } else {
+        None
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[path = "./test_anomaly.rs"]
+mod test_anomaly;
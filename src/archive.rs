@@ -0,0 +1,70 @@
+use std::io::{BufReader, Error, ErrorKind, Read, Result, Write};
+
+use zstd::stream::{read::Decoder, write::Encoder};
+
+use crate::record::Record;
+
+const MAGIC: &[u8; 8] = b"ELSYSAR1";
+
+/// Writes an archive of raw uplinks: the 8-byte magic `ELSYSAR1`, then a zstd stream of records
+/// in the `Record` encoding.
+pub struct Writer<W: Write> {
+    encoder: Encoder<'static, W>,
+}
+
+impl<W: Write> Writer<W> {
+    pub fn new(writer: W) -> Result<Self> {
+        Self::with_level(writer, zstd::DEFAULT_COMPRESSION_LEVEL)
+    }
+
+    pub fn with_level(mut writer: W, level: i32) -> Result<Self> {
+        writer.write_all(MAGIC)?;
+        Ok(Self {
+            encoder: Encoder::new(writer, level)?,
+        })
+    }
+
+    pub fn write(&mut self, record: &Record) -> Result<()> {
+        record.write_to(&mut self.encoder)
+    }
+
+    /// Completes the zstd stream. An archive which is dropped without being finished is truncated.
+    pub fn finish(self) -> Result<W> {
+        self.encoder.finish()
+    }
+}
+
+/// Streams the records of an archive without holding it in memory.
+pub struct Reader<R: Read> {
+    decoder: Decoder<'static, BufReader<R>>,
+}
+
+impl<R: Read> Reader<R> {
+    pub fn new(mut reader: R) -> Result<Self> {
+        let mut magic = [0; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("{:?} is not an Elsys archive header", magic),
+            ));
+        }
+
+        Ok(Self {
+            decoder: Decoder::new(reader)?,
+        })
+    }
+}
+
+impl<R: Read> Iterator for Reader<R> {
+    type Item = Result<Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Record::read_from(&mut self.decoder).transpose()
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[path = "./test_archive.rs"]
+mod test_archive;
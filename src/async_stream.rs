@@ -0,0 +1,127 @@
+use std::io::{Error, ErrorKind, Result};
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use futures_core::Stream;
+use tokio::io::{AsyncRead, ReadBuf};
+
+use crate::stream::{decode_hex, Framing};
+use crate::Uplink;
+
+/// The asynchronous counterpart of `UplinkStream`, with the same framing and error behaviour.
+pub struct AsyncUplinkStream<R: AsyncRead + Unpin> {
+    reader: R,
+    framing: Framing,
+    buffer: Vec<u8>,
+    finished: bool,
+}
+
+impl<R: AsyncRead + Unpin> AsyncUplinkStream<R> {
+    pub fn new(reader: R, framing: Framing) -> Self {
+        Self {
+            reader,
+            framing,
+            buffer: Vec::new(),
+            finished: false,
+        }
+    }
+
+    /// Takes the next payload out of the buffer, if it holds a complete one.
+    fn take_payload(&mut self, eof: bool) -> Option<Result<Vec<u8>>> {
+        match self.framing {
+            Framing::LengthPrefixed => {
+                let length = match self.buffer.get(..2) {
+                    Some(length) => u16::from_be_bytes([length[0], length[1]]) as usize,
+                    None if eof && !self.buffer.is_empty() => {
+                        return Some(Err(Error::from(ErrorKind::UnexpectedEof)))
+                    }
+                    None => return None,
+                };
+                if self.buffer.len() < 2 + length {
+                    return eof.then(|| Err(Error::from(ErrorKind::UnexpectedEof)));
+                }
+                let payload = self.buffer[2..2 + length].to_vec();
+                self.buffer.drain(..2 + length);
+                Some(Ok(payload))
+            }
+            Framing::HexLines => loop {
+                let end = match self.buffer.iter().position(|b| *b == b'\n') {
+                    Some(newline) => newline + 1,
+                    None if eof && !self.buffer.is_empty() => self.buffer.len(),
+                    None => return None,
+                };
+                let line = self.buffer.drain(..end).collect::<Vec<_>>();
+                let line = match std::str::from_utf8(&line) {
+                    Ok(line) => line.trim(),
+                    Err(e) => return Some(Err(Error::new(ErrorKind::InvalidData, e))),
+                };
+                if !line.is_empty() {
+                    return Some(decode_hex(line));
+                }
+            },
+        }
+    }
+}
+
+impl<R: AsyncRead + Unpin> Stream for AsyncUplinkStream<R> {
+    type Item = Result<Uplink>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let mut chunk = [0; 256];
+        loop {
+            if this.finished {
+                return Poll::Ready(None);
+            }
+            if let Some(payload) = this.take_payload(false) {
+                return Poll::Ready(Some(payload.and_then(|p| Uplink::deserialize(&p))));
+            }
+
+            let mut read = ReadBuf::new(&mut chunk);
+            if let Err(e) = ready!(Pin::new(&mut this.reader).poll_read(cx, &mut read)) {
+                this.finished = true;
+                return Poll::Ready(Some(Err(e)));
+            }
+            if read.filled().is_empty() {
+                // Complete frames were taken above, so only a final partial one can remain.
+                this.finished = true;
+                let payload = this.take_payload(true);
+                return Poll::Ready(payload.map(|p| p.and_then(|p| Uplink::deserialize(&p))));
+            }
+            this.buffer.extend_from_slice(read.filled());
+        }
+    }
+}
+
+/// Decodes each payload of a stream, such as a `Stream<Item = Bytes>` of message bodies.
+pub struct Decode<S> {
+    payloads: S,
+}
+
+impl<S> Decode<S> {
+    pub fn new(payloads: S) -> Self {
+        Self { payloads }
+    }
+}
+
+impl<S, P> Stream for Decode<S>
+where
+    S: Stream<Item = P> + Unpin,
+    P: AsRef<[u8]>,
+{
+    type Item = Result<Uplink>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        let payload = ready!(Pin::new(&mut self.payloads).poll_next(cx));
+        Poll::Ready(payload.map(|p| Uplink::deserialize(p.as_ref())))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.payloads.size_hint()
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[path = "./test_async_stream.rs"]
+mod test_async_stream;
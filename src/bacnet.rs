@@ -0,0 +1,196 @@
+use std::collections::{BTreeMap, HashMap};
+use std::io::{Error, ErrorKind, Result};
+
+use crate::{Field, Occupancy, Uplink};
+
+/// Instances reserved per device, leaving room for fields decoded in future.
+pub const INSTANCES_PER_DEVICE: u32 = 16;
+
+const _: () = assert!(Field::ALL.len() as u32 <= INSTANCES_PER_DEVICE);
+
+const MAX_INSTANCE: u32 = 4_194_302;
+
+/// The BACnet object types fields are exposed as, with their standard numbers.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum ObjectType {
+    AnalogInput = 0,
+    BinaryInput = 3,
+    MultiStateInput = 13,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct ObjectId {
+    pub object_type: ObjectType,
+    pub instance: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PresentValue {
+    Real(f32),
+    Binary(bool),
+    /// A multi-state value, numbered from 1.
+    State(u32),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Object {
+    pub id: ObjectId,
+    pub name: String,
+    /// The BACnet engineering units of an analog input.
+    pub units: Option<u16>,
+    /// The names of the states of a multi-state input.
+    pub state_text: &'static [&'static str],
+    pub present_value: PresentValue,
+}
+
+/// Decoded fields as BACnet input objects, for a BACnet server to serve.
+///
+/// Each device is given a number, and its fields are the instances from `number *
+/// INSTANCES_PER_DEVICE` onwards, in the order of `Field::ALL`. Objects appear once a field has
+/// been decoded.
+#[derive(Clone, Debug, Default)]
+pub struct Objects {
+    devices: HashMap<u64, u32>,
+    objects: BTreeMap<ObjectId, Object>,
+}
+
+impl Objects {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_device(&mut self, dev_eui: u64, number: u32) -> Result<()> {
+        let invalid = |message: String| Err(Error::new(ErrorKind::InvalidInput, message));
+        if number > MAX_INSTANCE / INSTANCES_PER_DEVICE {
+            return invalid(format!("device number {} is too large", number));
+        }
+        if let Some((other, _)) = self.devices.iter().find(|(_, n)| **n == number) {
+            return invalid(format!(
+                "device number {} is taken by {:016x}",
+                number, other
+            ));
+        }
+        self.devices.insert(dev_eui, number);
+        Ok(())
+    }
+
+    /// Updates the present values from an uplink, returning the objects whose value changed.
+    pub fn update(&mut self, dev_eui: u64, uplink: &Uplink) -> Vec<ObjectId> {
+        let Some(number) = self.devices.get(&dev_eui) else {
+            return Vec::new();
+        };
+
+        let mut changed = Vec::new();
+        for (index, field) in Field::ALL.iter().enumerate() {
+            let Some(present_value) = present_value(uplink, *field) else {
+                continue;
+            };
+            let id = ObjectId {
+                object_type: object_type(*field),
+                instance: number * INSTANCES_PER_DEVICE + index as u32,
+            };
+
+            match self.objects.get_mut(&id) {
+                Some(object) if object.present_value == present_value => continue,
+                Some(object) => object.present_value = present_value,
+                None => {
+                    self.objects.insert(
+                        id,
+                        Object {
+                            id,
+                            name: format!("{:016x}.{}", dev_eui, field.name()),
+                            units: units(*field),
+                            state_text: state_text(*field),
+                            present_value,
+                        },
+                    );
+                }
+            }
+            changed.push(id);
+        }
+        changed
+    }
+
+    pub fn get(&self, id: ObjectId) -> Option<&Object> {
+        self.objects.get(&id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Object> {
+        self.objects.values()
+    }
+}
+
+fn object_type(field: Field) -> ObjectType {
+    match field {
+        Field::Temperature
+        | Field::Co2
+        | Field::Battery
+        | Field::Humidity
+        | Field::Light
+        | Field::Motion
+        | Field::Pulse1
+        | Field::Pulse1Absolute
+        | Field::ExternalTemperature1
+        | Field::ExternalTemperature2
+        | Field::Distance
+        | Field::AccelerationMotion
+        | Field::Waterleak => ObjectType::AnalogInput,
+        Field::ExternalDigital => ObjectType::BinaryInput,
+        Field::Occupancy => ObjectType::MultiStateInput,
+    }
+}
+
+fn present_value(uplink: &Uplink, field: Field) -> Option<PresentValue> {
+    Some(match field {
+        Field::Temperature
+        | Field::Co2
+        | Field::Battery
+        | Field::Humidity
+        | Field::Light
+        | Field::Motion
+        | Field::Pulse1
+        | Field::Pulse1Absolute
+        | Field::ExternalTemperature1
+        | Field::ExternalTemperature2
+        | Field::Distance
+        | Field::AccelerationMotion
+        | Field::Waterleak => PresentValue::Real(uplink.value(field)?),
+        Field::ExternalDigital => PresentValue::Binary(uplink.external_digital()?),
+        Field::Occupancy => PresentValue::State(match uplink.occupancy()? {
+            Occupancy::NoBody => 1,
+            Occupancy::PendingOrPir => 2,
+            Occupancy::OccupiedOrHeat => 3,
+        }),
+    })
+}
+
+fn units(field: Field) -> Option<u16> {
+    match field {
+        Field::Temperature => Some(62),
+        Field::Co2 => Some(96),
+        Field::Battery => Some(5),
+        Field::Humidity => Some(29),
+        Field::Light => Some(37),
+        Field::Motion => Some(95),
+        Field::Pulse1 => Some(95),
+        Field::Pulse1Absolute => Some(95),
+        Field::ExternalTemperature1 => Some(62),
+        Field::ExternalTemperature2 => Some(62),
+        Field::Distance => Some(30),
+        Field::AccelerationMotion => Some(95),
+        Field::Waterleak => Some(95),
+        Field::Occupancy | Field::ExternalDigital => None,
+    }
+}
+
+fn state_text(field: Field) -> &'static [&'static str] {
+    match field {
+        Field::Occupancy => &["No body", "Pending or PIR", "Occupied or heat"],
+        _ => &[],
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[path = "./test_bacnet.rs"]
+mod test_bacnet;
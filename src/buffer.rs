@@ -0,0 +1,106 @@
+use std::io::{Error, Result};
+use std::path::Path;
+
+use redb::{Database, ReadableDatabase, ReadableTable, ReadableTableMetadata, TableDefinition};
+
+use crate::record::Record;
+
+const RECORDS: TableDefinition<u64, &[u8]> = TableDefinition::new("records");
+const SEQUENCE: TableDefinition<&str, u64> = TableDefinition::new("sequence");
+const NEXT: &str = "next";
+
+/// A bounded, persistent FIFO of raw uplink records, for buffering at the edge until the cloud
+/// confirms receipt. Each record is given an increasing sequence number, which is never reused.
+///
+/// Once `capacity` records are buffered, pushing another evicts the oldest.
+pub struct Buffer {
+    database: Database,
+    capacity: u64,
+}
+
+impl Buffer {
+    pub fn open<P: AsRef<Path>>(path: P, capacity: u64) -> Result<Self> {
+        let database = Database::create(path).map_err(other)?;
+        Ok(Self { database, capacity })
+    }
+
+    /// Buffers a record and returns its sequence number.
+    pub fn push(&self, record: &Record) -> Result<u64> {
+        let bytes = record.to_bytes()?;
+        let transaction = self.database.begin_write().map_err(other)?;
+        let sequence = {
+            let mut sequences = transaction.open_table(SEQUENCE).map_err(other)?;
+            let sequence = sequences.get(NEXT).map_err(other)?.map_or(0, |s| s.value());
+            sequences.insert(NEXT, sequence + 1).map_err(other)?;
+
+            let mut records = transaction.open_table(RECORDS).map_err(other)?;
+            records.insert(sequence, bytes.as_slice()).map_err(other)?;
+            while records.len().map_err(other)? > self.capacity {
+                records.pop_first().map_err(other)?;
+            }
+            sequence
+        };
+        transaction.commit().map_err(other)?;
+        Ok(sequence)
+    }
+
+    /// Up to `limit` buffered records, oldest first, with their sequence numbers.
+    pub fn pending(&self, limit: usize) -> Result<Vec<(u64, Record)>> {
+        let transaction = self.database.begin_read().map_err(other)?;
+        let records = match transaction.open_table(RECORDS) {
+            Ok(records) => records,
+            Err(redb::TableError::TableDoesNotExist(_)) => return Ok(Vec::new()),
+            Err(e) => return Err(other(e)),
+        };
+
+        records
+            .iter()
+            .map_err(other)?
+            .take(limit)
+            .map(|entry| {
+                let (sequence, bytes) = entry.map_err(other)?;
+                Ok((sequence.value(), Record::from_bytes(bytes.value())?))
+            })
+            .collect()
+    }
+
+    /// Removes every record up to and including sequence number `sequence`, returning how many were
+    /// removed.
+    pub fn acknowledge(&self, sequence: u64) -> Result<usize> {
+        let transaction = self.database.begin_write().map_err(other)?;
+        let mut removed = 0;
+        {
+            let mut records = transaction.open_table(RECORDS).map_err(other)?;
+            records
+                .retain_in(..=sequence, |_, _| {
+                    removed += 1;
+                    false
+                })
+                .map_err(other)?;
+        }
+        transaction.commit().map_err(other)?;
+        Ok(removed)
+    }
+
+    pub fn len(&self) -> Result<u64> {
+        let transaction = self.database.begin_read().map_err(other)?;
+        match transaction.open_table(RECORDS) {
+            Ok(records) => records.len().map_err(other),
+            Err(redb::TableError::TableDoesNotExist(_)) => Ok(0),
+            Err(e) => Err(other(e)),
+        }
+    }
+
+    pub fn is_empty(&self) -> Result<bool> {
+        Ok(self.len()? == 0)
+    }
+}
+
+fn other<E: Into<redb::Error>>(e: E) -> Error {
+    Error::other(e.into())
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[path = "./test_buffer.rs"]
+mod test_buffer;
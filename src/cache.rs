@@ -0,0 +1,78 @@
+use std::collections::{BTreeMap, HashMap};
+use std::io::Result;
+
+use crate::Uplink;
+
+/// Remembers the decodes of recent payloads, evicting the least recently used beyond `capacity`.
+/// Payloads which fail to decode are not cached.
+#[derive(Clone, Debug)]
+pub struct Cache {
+    capacity: usize,
+    entries: HashMap<Vec<u8>, (Uplink, u64)>,
+    recency: BTreeMap<u64, Vec<u8>>,
+    tick: u64,
+    hits: u64,
+    misses: u64,
+}
+
+impl Cache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: HashMap::new(),
+            recency: BTreeMap::new(),
+            tick: 0,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    pub fn deserialize(&mut self, input: &[u8]) -> Result<Uplink> {
+        self.tick += 1;
+
+        if let Some((uplink, last_used)) = self.entries.get_mut(input) {
+            self.hits += 1;
+            let key = self.recency.remove(last_used).unwrap_or_default();
+            *last_used = self.tick;
+            self.recency.insert(self.tick, key);
+            return Ok(uplink.clone());
+        }
+
+        self.misses += 1;
+        let uplink = Uplink::deserialize(input)?;
+        if self.capacity == 0 {
+            return Ok(uplink);
+        }
+        if self.entries.len() >= self.capacity {
+            if let Some((_, oldest)) = self.recency.pop_first() {
+                self.entries.remove(&oldest);
+            }
+        }
+        self.entries
+            .insert(input.to_vec(), (uplink.clone(), self.tick));
+        self.recency.insert(self.tick, input.to_vec());
+
+        Ok(uplink)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[path = "./test_cache.rs"]
+mod test_cache;
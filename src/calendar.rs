@@ -0,0 +1,176 @@
+use std::collections::BTreeSet;
+
+use crate::utilization::{DAY_S, HOUR_S};
+use crate::Uplink;
+
+/// When a building is in use: opening hours per weekday in local time, less holidays.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Calendar {
+    /// Seconds local time is ahead of UTC.
+    pub utc_offset_s: i64,
+    /// Opening and closing time of each weekday, Monday first, in seconds after local midnight.
+    pub hours: [Option<(u64, u64)>; 7],
+    /// Closed days, as numbered by `day_number`.
+    pub holidays: BTreeSet<u64>,
+}
+
+impl Calendar {
+    /// Open 08:00 to 18:00 local time, Monday to Friday.
+    pub fn office(utc_offset_s: i64) -> Self {
+        let weekday = Some((8 * HOUR_S, 18 * HOUR_S));
+        Self {
+            utc_offset_s,
+            hours: [weekday, weekday, weekday, weekday, weekday, None, None],
+            holidays: BTreeSet::new(),
+        }
+    }
+
+    pub fn add_holiday(&mut self, year: i64, month: i64, day: i64) {
+        self.holidays.insert(day_number(year, month, day));
+    }
+
+    pub fn contains(&self, timestamp_s: u64) -> bool {
+        self.open_s(timestamp_s, timestamp_s + 1) == 1
+    }
+
+    /// Seconds between `from_s` and `to_s` during which the building is open.
+    pub fn open_s(&self, from_s: u64, to_s: u64) -> u64 {
+        let (from_s, to_s) = (self.local_s(from_s), self.local_s(to_s));
+        let mut total_s = 0;
+
+        let mut day = from_s / DAY_S;
+        while day * DAY_S < to_s {
+            if let Some((open_s, close_s)) = self.hours_on(day) {
+                let start_s = (day * DAY_S + open_s).max(from_s);
+                let end_s = (day * DAY_S + close_s).min(to_s);
+                total_s += end_s.saturating_sub(start_s);
+            }
+            day += 1;
+        }
+
+        total_s
+    }
+
+    /// The readings taken while the building is open, for analytics over occupied hours only.
+    pub fn within(&self, readings: &[(u64, Uplink)]) -> Vec<(u64, Uplink)> {
+        readings
+            .iter()
+            .filter(|(timestamp_s, _)| self.contains(*timestamp_s))
+            .cloned()
+            .collect()
+    }
+
+    fn local_s(&self, timestamp_s: u64) -> u64 {
+        timestamp_s.saturating_add_signed(self.utc_offset_s)
+    }
+
+    fn hours_on(&self, day: u64) -> Option<(u64, u64)> {
+        if self.holidays.contains(&day) {
+            return None;
+        }
+        // 1970-01-01 was a Thursday.
+        self.hours[((day + 3) % 7) as usize]
+    }
+}
+
+/// Days since 1970-01-01 of a date, for `Calendar::holidays`.
+pub fn day_number(year: i64, month: i64, day: i64) -> u64 {
+    days_from_civil(year, month, day).max(0) as u64
+}
+
+/// Days since 1970-01-01 of a date in the proleptic Gregorian calendar.
+pub(crate) fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// Year, month and day of a number of days since 1970-01-01, the inverse of `days_from_civil`.
+pub(crate) fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month + 2) / 5 + 1;
+    let month = if month < 10 { month + 3 } else { month - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// A timestamp in seconds since the Unix epoch in the form `2024-03-01T12:34:56Z`.
+pub(crate) fn format_rfc3339_s(timestamp_s: u64) -> String {
+    let (year, month, day) = civil_from_days((timestamp_s / DAY_S) as i64);
+    let second_of_day = timestamp_s % DAY_S;
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        second_of_day / HOUR_S,
+        second_of_day % HOUR_S / 60,
+        second_of_day % 60
+    )
+}
+
+/// Seconds since the Unix epoch of a timestamp such as `2024-03-01T12:34:56.789Z`. Fractions of a
+/// second are truncated.
+#[cfg(any(feature = "json", feature = "ttn"))]
+pub(crate) fn parse_rfc3339_s(timestamp: &str) -> Option<u64> {
+    let number = |range: std::ops::Range<usize>| -> Option<i64> {
+        let digits = timestamp.get(range)?;
+        if !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        digits.parse().ok()
+    };
+    let separators = [(4, b'-'), (7, b'-'), (13, b':'), (16, b':')];
+    if separators
+        .iter()
+        .any(|(i, separator)| timestamp.as_bytes().get(*i) != Some(separator))
+        || !matches!(timestamp.as_bytes().get(10), Some(b'T' | b't' | b' '))
+    {
+        return None;
+    }
+
+    let (year, month, day) = (number(0..4)?, number(5..7)?, number(8..10)?);
+    let (hour, minute, second) = (number(11..13)?, number(14..16)?, number(17..19)?);
+    if !(1..=12).contains(&month)
+        || !(1..=31).contains(&day)
+        || hour > 23
+        || minute > 59
+        || second > 60
+    {
+        return None;
+    }
+
+    let rest = timestamp.get(19..)?;
+    let zone = rest.trim_start_matches(|c: char| c == '.' || c.is_ascii_digit());
+    let offset_s = match zone.as_bytes() {
+        [b'Z' | b'z'] => 0,
+        [sign @ (b'+' | b'-'), _, _, b':', _, _] => {
+            let hours = zone.get(1..3)?.parse::<i64>().ok()?;
+            let minutes = zone.get(4..6)?.parse::<i64>().ok()?;
+            let offset_s = hours * 3600 + minutes * 60;
+            if *sign == b'+' {
+                offset_s
+            } else {
+                -offset_s
+            }
+        }
+        _ => return None,
+    };
+
+    let days = days_from_civil(year, month, day);
+    u64::try_from(days * 86400 + hour * 3600 + minute * 60 + second - offset_s).ok()
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[path = "./test_calendar.rs"]
+mod test_calendar;
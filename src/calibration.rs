@@ -0,0 +1,121 @@
+use std::collections::HashMap;
+use std::io::Result;
+
+use crate::Uplink;
+
+/// Corrections applied to raw readings: `co2 * co2_slope + co2_offset_ppm`,
+/// `temperature + temperature_offset`, `humidity + humidity_offset_percent`, and each external
+/// temperature plus its own offset. Motion counts are left as they are, and `footfall` scales them.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Calibration {
+    pub temperature_offset: f32,
+    pub co2_offset_ppm: f32,
+    pub co2_slope: f32,
+    pub humidity_offset_percent: f32,
+    pub external_temperature_1_offset: f32,
+    pub external_temperature_2_offset: f32,
+    /// Motion counts per person walking past, which depends on where and how high the sensor is
+    /// mounted. Found by counting people by hand over a period and dividing the motion counts over
+    /// the period by the people counted.
+    pub motion_per_person: f32,
+}
+
+impl Default for Calibration {
+    fn default() -> Self {
+        Self {
+            temperature_offset: 0.0,
+            co2_offset_ppm: 0.0,
+            co2_slope: 1.0,
+            humidity_offset_percent: 0.0,
+            external_temperature_1_offset: 0.0,
+            external_temperature_2_offset: 0.0,
+            motion_per_person: 1.0,
+        }
+    }
+}
+
+impl Calibration {
+    /// The approximate number of people who walked past during the readings, from their motion
+    /// counts. `None` for readings without motion counts or a `motion_per_person` that is not
+    /// positive.
+    pub fn footfall(&self, readings: &[(u64, Uplink)]) -> Option<f32> {
+        let motion_per_person = Some(self.motion_per_person).filter(|m| *m > 0.0)?;
+        let counts = readings
+            .iter()
+            .filter_map(|(_, uplink)| uplink.motion_count())
+            .map(u32::from)
+            .reduce(|a, b| a + b)?;
+        Some(counts as f32 / motion_per_person)
+    }
+}
+
+/// Calibrations per device, keyed by DevEUI.
+#[derive(Clone, Debug, Default)]
+pub struct Calibrations {
+    devices: HashMap<u64, Calibration>,
+}
+
+impl Uplink {
+    pub fn apply_calibration(&mut self, calibration: &Calibration) {
+        if let Some(temperature) = self.temperature.as_mut() {
+            *temperature += calibration.temperature_offset;
+        }
+        if let Some(co2) = self.co2.as_mut() {
+            let corrected = *co2 as f32 * calibration.co2_slope + calibration.co2_offset_ppm;
+            *co2 = corrected.round().clamp(0.0, u16::MAX as f32) as u16;
+        }
+        if let Some(humidity) = self.humidity.as_mut() {
+            let corrected = *humidity as f32 + calibration.humidity_offset_percent;
+            *humidity = corrected.round().clamp(0.0, 100.0) as u8;
+        }
+        if let Some(temperature) = self.external_temperature_1.as_mut() {
+            *temperature += calibration.external_temperature_1_offset;
+        }
+        if let Some(temperature) = self.external_temperature_2.as_mut() {
+            *temperature += calibration.external_temperature_2_offset;
+        }
+    }
+}
+
+impl Calibrations {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, dev_eui: u64, calibration: Calibration) -> Option<Calibration> {
+        self.devices.insert(dev_eui, calibration)
+    }
+
+    pub fn remove(&mut self, dev_eui: u64) -> Option<Calibration> {
+        self.devices.remove(&dev_eui)
+    }
+
+    pub fn get(&self, dev_eui: u64) -> Option<&Calibration> {
+        self.devices.get(&dev_eui)
+    }
+
+    /// Footfall as for `Calibration::footfall`, with the device's calibration or, if none is
+    /// registered, one motion count per person.
+    pub fn footfall(&self, dev_eui: u64, readings: &[(u64, Uplink)]) -> Option<f32> {
+        match self.get(dev_eui) {
+            Some(calibration) => calibration.footfall(readings),
+            None => Calibration::default().footfall(readings),
+        }
+    }
+
+    /// Deserializes an uplink and applies the device's calibration, if one is registered.
+    pub fn deserialize(&self, dev_eui: u64, input: &[u8]) -> Result<Uplink> {
+        let mut output = Uplink::deserialize(input)?;
+        if let Some(calibration) = self.get(dev_eui) {
+            output.apply_calibration(calibration);
+        }
+        Ok(output)
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[path = "./test_calibration.rs"]
+mod test_calibration;
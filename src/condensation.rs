@@ -0,0 +1,49 @@
+use crate::Uplink;
+
+/// The Magnus coefficients of Alduchov and Eskridge (1996), for -45 to 60 °C over water.
+const MAGNUS_B: f32 = 17.62;
+const MAGNUS_C: f32 = 243.12;
+
+/// The dew point (°C) of air at `temperature` (°C) and `humidity_percent` relative humidity.
+/// `None` for a humidity of 0 or above 100 %.
+pub fn dew_point(temperature: f32, humidity_percent: f32) -> Option<f32> {
+    if !(humidity_percent > 0.0 && humidity_percent <= 100.0) {
+        return None;
+    }
+    let gamma = (humidity_percent / 100.0).ln() + MAGNUS_B * temperature / (MAGNUS_C + temperature);
+    Some(MAGNUS_C * gamma / (MAGNUS_B - gamma))
+}
+
+/// Quantities computed from several fields of an uplink, for use in rules.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Derived {
+    /// The dew point of the air at the sensor, from the internal temperature and humidity.
+    DewPoint,
+    /// How far the surface under the first external probe is above the dew point of the air, in
+    /// °C. Condensation forms on the surface at 0 and below.
+    DewPointMargin,
+}
+
+impl Derived {
+    pub fn name(self) -> &'static str {
+        match self {
+            Derived::DewPoint => "dew_point",
+            Derived::DewPointMargin => "dew_point_margin",
+        }
+    }
+
+    pub fn value(self, uplink: &Uplink) -> Option<f32> {
+        let dew_point = dew_point(uplink.temperature()?, f32::from(uplink.humidity_percent()?))?;
+        match self {
+            Derived::DewPoint => Some(dew_point),
+            Derived::DewPointMargin => Some(uplink.external_temperature_1()? - dew_point),
+        }
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[path = "./test_condensation.rs"]
+mod test_condensation;
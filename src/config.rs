@@ -0,0 +1,131 @@
+use std::fs;
+use std::io::{Error, ErrorKind, Result};
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use serde::Deserialize;
+
+use crate::calibration::Calibrations;
+use crate::rules::{Engine, Rule};
+use crate::{Calibration, Limits};
+
+/// The decoder pipeline as configured from one TOML file.
+#[derive(Clone, Debug, Default)]
+pub struct Config {
+    pub limits: Limits,
+    pub calibrations: Calibrations,
+    pub rules: Vec<Rule>,
+}
+
+#[derive(Deserialize)]
+struct File {
+    #[serde(default)]
+    limits: Limits,
+    #[serde(default)]
+    device: Vec<Device>,
+    #[serde(default)]
+    rule: Vec<Rule>,
+}
+
+#[derive(Deserialize)]
+struct Device {
+    dev_eui: String,
+    #[serde(default)]
+    calibration: Calibration,
+}
+
+impl Config {
+    /// Every section is optional, and missing values take their defaults:
+    ///
+    /// ```toml
+    /// [limits]
+    /// max_payload_bytes = 51
+    ///
+    /// [[device]]
+    /// dev_eui = "a81758fffe000001"
+    /// calibration = { temperature_offset = -0.5, co2_offset_ppm = 20.0 }
+    ///
+    /// [[rule]]
+    /// name = "co2 high"
+    /// field = "co2"
+    /// operator = "above"
+    /// threshold = 1000.0
+    /// severity = "warning"
+    /// ```
+    ///
+    /// Rules are as for `Engine::from_toml`.
+    pub fn from_toml(input: &str) -> Result<Self> {
+        let file: File = toml::from_str(input).map_err(|e| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("not a valid configuration: {}", e),
+            )
+        })?;
+
+        let mut calibrations = Calibrations::new();
+        for device in file.device {
+            let dev_eui = u64::from_str_radix(&device.dev_eui, 16).map_err(|_| {
+                Error::new(
+                    ErrorKind::InvalidData,
+                    format!("{} is not a hexadecimal DevEUI", device.dev_eui),
+                )
+            })?;
+            if calibrations.insert(dev_eui, device.calibration).is_some() {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("device {:016x} is configured more than once", dev_eui),
+                ));
+            }
+        }
+
+        Ok(Self {
+            limits: file.limits,
+            calibrations,
+            rules: file.rule,
+        })
+    }
+
+    /// A rule engine for one device, with the configured rules.
+    pub fn engine(&self) -> Engine {
+        let mut output = Engine::new();
+        for rule in &self.rules {
+            output.add(rule.clone());
+        }
+        output
+    }
+}
+
+/// Reloads a configuration file when its modification time changes, so that devices and rules can
+/// be updated without restarting. Call `poll` periodically.
+#[derive(Clone, Debug)]
+pub struct Watcher {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+}
+
+impl Watcher {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            modified: None,
+        }
+    }
+
+    /// Returns the configuration on the first poll and after each modification, and `None` while
+    /// the file is unchanged. An invalid file is reported once, and the previous configuration
+    /// should stay in use until the file is modified again.
+    pub fn poll(&mut self) -> Result<Option<Config>> {
+        let modified = fs::metadata(&self.path)?.modified()?;
+        if self.modified == Some(modified) {
+            return Ok(None);
+        }
+
+        self.modified = Some(modified);
+        Config::from_toml(&fs::read_to_string(&self.path)?).map(Some)
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[path = "./test_config.rs"]
+mod test_config;
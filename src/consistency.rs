@@ -0,0 +1,89 @@
+use crate::utilization::{DAY_S, HOUR_S};
+use crate::{Occupancy, Uplink};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConsistencyConfig {
+    /// How long a room may be reported occupied while no motion is counted.
+    pub max_still_s: u64,
+    /// Seconds local time is ahead of UTC.
+    pub utc_offset_s: i64,
+    /// When an occupied room should not be dark, in seconds after local midnight.
+    pub daylight_s: (u64, u64),
+}
+
+impl Default for ConsistencyConfig {
+    fn default() -> Self {
+        Self {
+            max_still_s: 4 * HOUR_S,
+            utc_offset_s: 0,
+            daylight_s: (10 * HOUR_S, 14 * HOUR_S),
+        }
+    }
+}
+
+/// A sign of a mis-mounted or failing sensor.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Diagnostic {
+    /// Occupied since `since_s`, `still_s` ago, without any motion counted. Raised once until
+    /// motion is counted or the room is free.
+    OccupiedWithoutMotion { since_s: u64, still_s: u64 },
+    /// No light at `at_s`, during daylight in an occupied room.
+    DarkWhileOccupied { at_s: u64 },
+}
+
+/// Cross-checks the occupancy of one device against its motion count and light. Uplinks must be
+/// pushed in chronological order.
+#[derive(Clone, Debug)]
+pub struct Checker {
+    config: ConsistencyConfig,
+    still_since_s: Option<u64>,
+    reported: bool,
+}
+
+impl Checker {
+    pub fn new(config: ConsistencyConfig) -> Self {
+        Self {
+            config,
+            still_since_s: None,
+            reported: false,
+        }
+    }
+
+    pub fn push(&mut self, timestamp_s: u64, uplink: &Uplink) -> Vec<Diagnostic> {
+        let mut output = Vec::new();
+        let occupied = uplink.occupancy().map(|o| o == Occupancy::OccupiedOrHeat);
+
+        match (occupied, uplink.motion_count()) {
+            (Some(true), Some(0)) => {
+                let since_s = *self.still_since_s.get_or_insert(timestamp_s);
+                let still_s = timestamp_s.saturating_sub(since_s);
+                if !self.reported && still_s > self.config.max_still_s {
+                    self.reported = true;
+                    output.push(Diagnostic::OccupiedWithoutMotion { since_s, still_s });
+                }
+            }
+            (Some(false), _) | (_, Some(1..)) => {
+                self.still_since_s = None;
+                self.reported = false;
+            }
+            (Some(true), None) | (None, _) => {}
+        }
+
+        let (from_s, to_s) = self.config.daylight_s;
+        let time_of_day_s = timestamp_s.saturating_add_signed(self.config.utc_offset_s) % DAY_S;
+        if occupied == Some(true)
+            && uplink.light_lux() == Some(0)
+            && (from_s..to_s).contains(&time_of_day_s)
+        {
+            output.push(Diagnostic::DarkWhileOccupied { at_s: timestamp_s });
+        }
+
+        output
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[path = "./test_consistency.rs"]
+mod test_consistency;
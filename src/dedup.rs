@@ -0,0 +1,82 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashSet, VecDeque};
+use std::hash::{Hash, Hasher};
+use std::io::Result;
+
+use crate::Uplink;
+
+type Key = (u64, u32, u64);
+
+/// Drops repeats of a frame received through several gateways or webhooks.
+///
+/// A frame is identified by its DevEUI, frame counter and a hash of its payload, and is remembered
+/// for `window_s` seconds after it was first seen. Frames are expected in roughly chronological
+/// order.
+#[derive(Clone, Debug)]
+pub struct Deduplicator {
+    window_s: u64,
+    seen: HashSet<Key>,
+    expiry: VecDeque<(u64, Key)>,
+}
+
+impl Deduplicator {
+    pub fn new(window_s: u64) -> Self {
+        Self {
+            window_s,
+            seen: HashSet::new(),
+            expiry: VecDeque::new(),
+        }
+    }
+
+    /// Returns `true` the first time a frame is seen within the window.
+    pub fn push(
+        &mut self,
+        timestamp_s: u64,
+        dev_eui: u64,
+        frame_counter: u32,
+        payload: &[u8],
+    ) -> bool {
+        while let Some((first_seen_s, key)) = self.expiry.front() {
+            if timestamp_s.saturating_sub(*first_seen_s) < self.window_s {
+                break;
+            }
+            self.seen.remove(key);
+            self.expiry.pop_front();
+        }
+
+        let mut hasher = DefaultHasher::new();
+        payload.hash(&mut hasher);
+        let key = (dev_eui, frame_counter, hasher.finish());
+
+        let first = self.seen.insert(key);
+        if first {
+            self.expiry.push_back((timestamp_s, key));
+        }
+        first
+    }
+
+    /// Deserializes the payload the first time the frame is seen, and returns `None` for repeats.
+    pub fn deserialize(
+        &mut self,
+        timestamp_s: u64,
+        dev_eui: u64,
+        frame_counter: u32,
+        payload: &[u8],
+    ) -> Option<Result<Uplink>> {
+        self.push(timestamp_s, dev_eui, frame_counter, payload)
+            .then(|| Uplink::deserialize(payload))
+    }
+
+    pub fn len(&self) -> usize {
+        self.seen.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.seen.is_empty()
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[path = "./test_dedup.rs"]
+mod test_dedup;
@@ -0,0 +1,75 @@
+use crate::utilization::DAY_S;
+use crate::Uplink;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DegreeDayConfig {
+    pub heating_base: f32,
+    pub cooling_base: f32,
+    /// Seconds after midnight UTC at which each day begins, such as local midnight.
+    pub day_start_s: u64,
+}
+
+impl Default for DegreeDayConfig {
+    fn default() -> Self {
+        Self {
+            heating_base: 18.0,
+            cooling_base: 18.0,
+            day_start_s: 0,
+        }
+    }
+}
+
+/// Degree-days of one day, from the mean of that day's temperature readings.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DegreeDays {
+    pub start_s: u64,
+    pub mean_temperature: f32,
+    pub heating: f32,
+    pub cooling: f32,
+}
+
+/// Degree-days per day with temperature readings. `readings` must be sorted by timestamp.
+pub fn daily(readings: &[(u64, Uplink)], config: &DegreeDayConfig) -> Vec<DegreeDays> {
+    let mut days: Vec<(u64, f32, u32)> = Vec::new();
+
+    for (timestamp_s, uplink) in readings {
+        let Some(temperature) = uplink.temperature() else {
+            continue;
+        };
+        let shifted_s = timestamp_s.saturating_sub(config.day_start_s);
+        let start_s = shifted_s - shifted_s % DAY_S + config.day_start_s;
+
+        match days.last_mut() {
+            Some((last_s, sum, count)) if *last_s == start_s => {
+                *sum += temperature;
+                *count += 1;
+            }
+            _ => days.push((start_s, temperature, 1)),
+        }
+    }
+
+    days.into_iter()
+        .map(|(start_s, sum, count)| {
+            let mean_temperature = sum / count as f32;
+            DegreeDays {
+                start_s,
+                mean_temperature,
+                heating: (config.heating_base - mean_temperature).max(0.0),
+                cooling: (mean_temperature - config.cooling_base).max(0.0),
+            }
+        })
+        .collect()
+}
+
+/// Heating and cooling degree-days summed over the days.
+pub fn total(days: &[DegreeDays]) -> (f32, f32) {
+    days.iter().fold((0.0, 0.0), |(heating, cooling), day| {
+        (heating + day.heating, cooling + day.cooling)
+    })
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[path = "./test_degree_days.rs"]
+mod test_degree_days;
@@ -0,0 +1,245 @@
+use crate::{Acceleration, Field, GpsPosition, Occupancy, Uplink};
+
+/// A value together with the time it was received, in seconds since the Unix epoch.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Timestamped<T> {
+    pub value: T,
+    pub timestamp_s: u64,
+}
+
+/// The latest known value of every field of one device, folded from successive uplinks.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeviceState {
+    last_uplink_s: Option<u64>,
+    temperature: Option<Timestamped<f32>>,
+    co2: Option<Timestamped<u16>>,
+    battery_mv: Option<Timestamped<u16>>,
+    occupancy: Option<Timestamped<Occupancy>>,
+    external_digital: Option<Timestamped<bool>>,
+    humidity: Option<Timestamped<u8>>,
+    light: Option<Timestamped<u16>>,
+    motion: Option<Timestamped<u8>>,
+    pulse1: Option<Timestamped<u16>>,
+    pulse1_absolute: Option<Timestamped<u32>>,
+    external_temperature_1: Option<Timestamped<f32>>,
+    external_temperature_2: Option<Timestamped<f32>>,
+    distance: Option<Timestamped<u16>>,
+    acceleration_motion: Option<Timestamped<u8>>,
+    waterleak: Option<Timestamped<u8>>,
+    acceleration: Option<Timestamped<[i8; 3]>>,
+    gps: Option<Timestamped<[i32; 2]>>,
+}
+
+impl DeviceState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fields absent from `uplink` keep their previous value. An uplink older than a stored value
+    /// does not overwrite it, so uplinks may be applied out of order.
+    pub fn update(&mut self, timestamp_s: u64, uplink: &Uplink) {
+        if self.last_uplink_s.is_none_or(|last| last < timestamp_s) {
+            self.last_uplink_s = Some(timestamp_s);
+        }
+
+        merge(&mut self.temperature, uplink.temperature, timestamp_s);
+        merge(&mut self.co2, uplink.co2, timestamp_s);
+        merge(&mut self.battery_mv, uplink.battery_mv, timestamp_s);
+        merge(&mut self.occupancy, uplink.occupancy, timestamp_s);
+        merge(
+            &mut self.external_digital,
+            uplink.external_digital,
+            timestamp_s,
+        );
+        merge(&mut self.humidity, uplink.humidity, timestamp_s);
+        merge(&mut self.light, uplink.light, timestamp_s);
+        merge(&mut self.motion, uplink.motion, timestamp_s);
+        merge(&mut self.pulse1, uplink.pulse1, timestamp_s);
+        merge(
+            &mut self.pulse1_absolute,
+            uplink.pulse1_absolute,
+            timestamp_s,
+        );
+        merge(
+            &mut self.external_temperature_1,
+            uplink.external_temperature_1,
+            timestamp_s,
+        );
+        merge(
+            &mut self.external_temperature_2,
+            uplink.external_temperature_2,
+            timestamp_s,
+        );
+        merge(&mut self.distance, uplink.distance, timestamp_s);
+        merge(
+            &mut self.acceleration_motion,
+            uplink.acceleration_motion,
+            timestamp_s,
+        );
+        merge(&mut self.waterleak, uplink.waterleak, timestamp_s);
+        merge(&mut self.acceleration, uplink.acceleration, timestamp_s);
+        merge(&mut self.gps, uplink.gps, timestamp_s);
+    }
+
+    pub fn last_uplink_s(&self) -> Option<u64> {
+        self.last_uplink_s
+    }
+
+    pub fn temperature(&self) -> Option<Timestamped<f32>> {
+        self.temperature
+    }
+
+    pub fn co2_ppm(&self) -> Option<Timestamped<u16>> {
+        self.co2
+    }
+
+    pub fn battery_voltage(&self) -> Option<Timestamped<f32>> {
+        self.battery_mv.map(|bmv| Timestamped {
+            value: bmv.value as f32 * 0.001,
+            timestamp_s: bmv.timestamp_s,
+        })
+    }
+
+    pub fn occupancy(&self) -> Option<Timestamped<Occupancy>> {
+        self.occupancy
+    }
+
+    pub fn external_digital(&self) -> Option<Timestamped<bool>> {
+        self.external_digital
+    }
+
+    pub fn humidity_percent(&self) -> Option<Timestamped<u8>> {
+        self.humidity
+    }
+
+    pub fn light_lux(&self) -> Option<Timestamped<u16>> {
+        self.light
+    }
+
+    pub fn motion_count(&self) -> Option<Timestamped<u8>> {
+        self.motion
+    }
+
+    pub fn pulse1(&self) -> Option<Timestamped<u16>> {
+        self.pulse1
+    }
+
+    pub fn pulse1_absolute(&self) -> Option<Timestamped<u32>> {
+        self.pulse1_absolute
+    }
+
+    pub fn external_temperature_1(&self) -> Option<Timestamped<f32>> {
+        self.external_temperature_1
+    }
+
+    pub fn external_temperature_2(&self) -> Option<Timestamped<f32>> {
+        self.external_temperature_2
+    }
+
+    pub fn distance_mm(&self) -> Option<Timestamped<u16>> {
+        self.distance
+    }
+
+    pub fn acceleration_motion(&self) -> Option<Timestamped<u8>> {
+        self.acceleration_motion
+    }
+
+    pub fn waterleak(&self) -> Option<Timestamped<u8>> {
+        self.waterleak
+    }
+
+    pub fn acceleration(&self) -> Option<Timestamped<Acceleration>> {
+        self.acceleration.map(|a| Timestamped {
+            value: Acceleration::from_raw(a.value),
+            timestamp_s: a.timestamp_s,
+        })
+    }
+
+    pub fn gps(&self) -> Option<Timestamped<GpsPosition>> {
+        self.gps.map(|g| Timestamped {
+            value: GpsPosition::from_raw(g.value),
+            timestamp_s: g.timestamp_s,
+        })
+    }
+
+    /// The latest value of every field, regardless of when each was received.
+    pub fn latest(&self) -> Uplink {
+        Uplink {
+            temperature: self.temperature.map(|t| t.value),
+            co2: self.co2.map(|t| t.value),
+            battery_mv: self.battery_mv.map(|t| t.value),
+            occupancy: self.occupancy.map(|t| t.value),
+            external_digital: self.external_digital.map(|t| t.value),
+            humidity: self.humidity.map(|t| t.value),
+            light: self.light.map(|t| t.value),
+            motion: self.motion.map(|t| t.value),
+            pulse1: self.pulse1.map(|t| t.value),
+            pulse1_absolute: self.pulse1_absolute.map(|t| t.value),
+            external_temperature_1: self.external_temperature_1.map(|t| t.value),
+            external_temperature_2: self.external_temperature_2.map(|t| t.value),
+            distance: self.distance.map(|t| t.value),
+            acceleration_motion: self.acceleration_motion.map(|t| t.value),
+            waterleak: self.waterleak.map(|t| t.value),
+            acceleration: self.acceleration.map(|t| t.value),
+            gps: self.gps.map(|t| t.value),
+            undecoded: Vec::new(),
+        }
+    }
+
+    /// The latest value of a field as a number, as `Uplink::value`.
+    pub fn value(&self, field: Field) -> Option<Timestamped<f32>> {
+        Some(Timestamped {
+            value: self.latest().value(field)?,
+            timestamp_s: self.updated_s(field)?,
+        })
+    }
+
+    pub fn updated_s(&self, field: Field) -> Option<u64> {
+        match field {
+            Field::Temperature => self.temperature.map(|t| t.timestamp_s),
+            Field::Co2 => self.co2.map(|t| t.timestamp_s),
+            Field::Battery => self.battery_mv.map(|t| t.timestamp_s),
+            Field::Occupancy => self.occupancy.map(|t| t.timestamp_s),
+            Field::ExternalDigital => self.external_digital.map(|t| t.timestamp_s),
+            Field::Humidity => self.humidity.map(|t| t.timestamp_s),
+            Field::Light => self.light.map(|t| t.timestamp_s),
+            Field::Motion => self.motion.map(|t| t.timestamp_s),
+            Field::Pulse1 => self.pulse1.map(|t| t.timestamp_s),
+            Field::Pulse1Absolute => self.pulse1_absolute.map(|t| t.timestamp_s),
+            Field::ExternalTemperature1 => self.external_temperature_1.map(|t| t.timestamp_s),
+            Field::ExternalTemperature2 => self.external_temperature_2.map(|t| t.timestamp_s),
+            Field::Distance => self.distance.map(|t| t.timestamp_s),
+            Field::AccelerationMotion => self.acceleration_motion.map(|t| t.timestamp_s),
+            Field::Waterleak => self.waterleak.map(|t| t.timestamp_s),
+        }
+    }
+
+    /// A field which has never been received is not stale.
+    pub fn is_stale(&self, field: Field, now_s: u64, max_age_s: u64) -> bool {
+        self.updated_s(field)
+            .is_some_and(|updated_s| now_s.saturating_sub(updated_s) > max_age_s)
+    }
+
+    pub fn stale_fields(&self, now_s: u64, max_age_s: u64) -> Vec<Field> {
+        Field::ALL
+            .iter()
+            .copied()
+            .filter(|field| self.is_stale(*field, now_s, max_age_s))
+            .collect()
+    }
+}
+
+fn merge<T>(state: &mut Option<Timestamped<T>>, value: Option<T>, timestamp_s: u64) {
+    if let Some(value) = value {
+        if state.as_ref().is_none_or(|s| s.timestamp_s <= timestamp_s) {
+            *state = Some(Timestamped { value, timestamp_s });
+        }
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[path = "./test_device_state.rs"]
+mod test_device_state;
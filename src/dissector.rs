@@ -0,0 +1,149 @@
+use std::fmt::Write;
+
+use crate::uplink::records;
+
+/// How a record's value is shown.
+enum Format {
+    Unsigned(&'static str),
+    Signed(&'static str),
+    /// A signed value in tenths.
+    Tenths(&'static str),
+    /// Text computed by a Lua expression of the record's `range`.
+    Text(&'static str, &'static str),
+    Bytes,
+}
+
+/// The format of each record, following the Elsys payload specification. Records without one are
+/// shown as bytes.
+fn format(identifier: u8) -> Format {
+    match identifier {
+        0x01 | 0x0c | 0x19 => Format::Tenths(" °C"),
+        0x02 => Format::Unsigned(" %"),
+        0x03 => Format::Text(
+            r#"string.format("%d %d %d", range(0, 1):int(), range(1, 1):int(), range(2, 1):int())"#,
+            " (63 = 1 G)",
+        ),
+        0x04 => Format::Unsigned(" lux"),
+        0x06 => Format::Unsigned(" ppm"),
+        0x07 | 0x08 | 0x18 => Format::Unsigned(" mV"),
+        0x09 => Format::Text(
+            concat!(
+                r#"string.format("%.4f, %.4f", "#,
+                "range(0, 3):le_int() / 10000, range(3, 3):le_int() / 10000)",
+            ),
+            "",
+        ),
+        0x05 | 0x0a | 0x0b | 0x0d | 0x0f | 0x11 | 0x12 | 0x16 | 0x17 | 0x1a => Format::Unsigned(""),
+        0x0e => Format::Unsigned(" mm"),
+        0x10 => Format::Text(
+            r#"string.format("%.1f, %.1f", range(0, 2):int() / 10, range(2, 2):int() / 10)"#,
+            " °C",
+        ),
+        0x14 => Format::Text(r#"string.format("%.3f", range:uint() / 1000)"#, " hPa"),
+        0x15 => Format::Text(
+            r#"string.format("peak %d, average %d", range(0, 1):uint(), range(1, 1):uint())"#,
+            " dB",
+        ),
+        0x1b => Format::Signed(" uV"),
+        0x1c => Format::Unsigned(" ppb"),
+        _ => Format::Bytes,
+    }
+}
+
+/// Generates a Wireshark dissector in Lua for Elsys payloads. Record identifiers, names and sizes
+/// come from the decoder's own layout, but how each value is scaled and shown comes from `format`,
+/// which must be kept in step with the decoder by hand. Each record is shown by name with its
+/// decoded value and unit; unknown identifiers and truncated records are flagged as malformed.
+///
+/// The protocol is registered as `elsys`, to be selected with Decode As or added to the table of
+/// the dissector carrying the payload.
+pub fn lua() -> String {
+    let mut output = String::new();
+    let _ = writeln!(
+        output,
+        "-- Generated by elsys::dissector::lua; do not edit."
+    );
+    let _ = writeln!(output, "local elsys = Proto(\"elsys\", \"Elsys uplink\")");
+    let _ = writeln!(output, "local fields = {{}}");
+    let _ = writeln!(output, "local values = {{}}");
+    let _ = writeln!(output, "local units = {{}}");
+    let _ = writeln!(output, "local names = {{}}");
+    let _ = writeln!(output, "local sizes = {{}}");
+
+    for (identifier, size, name) in records() {
+        let bits = match size {
+            1 => Some("8"),
+            2 => Some("16"),
+            3 => Some("24"),
+            4 => Some("32"),
+            _ => None,
+        };
+        let (protofield, value, unit) = match (format(identifier), bits) {
+            (Format::Unsigned(unit), Some(bits)) => (format!("uint{}", bits), None, unit),
+            (Format::Signed(unit), Some(bits)) => (format!("int{}", bits), None, unit),
+            (Format::Tenths(unit), _) => ("float".to_string(), Some("range:int() / 10"), unit),
+            (Format::Text(value, unit), _) => ("string".to_string(), Some(value), unit),
+            _ => ("bytes".to_string(), None, ""),
+        };
+        let abbreviation = name.to_lowercase().replace(' ', "_");
+        let _ = writeln!(
+            output,
+            "fields[0x{:02x}] = ProtoField.{}(\"elsys.{}\", \"{}\")",
+            identifier, protofield, abbreviation, name
+        );
+        if let Some(value) = value {
+            let _ = writeln!(
+                output,
+                "values[0x{:02x}] = function(range) return {} end",
+                identifier, value
+            );
+        }
+        if !unit.is_empty() {
+            let _ = writeln!(output, "units[0x{:02x}] = \"{}\"", identifier, unit);
+        }
+        let _ = writeln!(output, "names[0x{:02x}] = \"{}\"", identifier, name);
+        let _ = writeln!(output, "sizes[0x{:02x}] = {}", identifier, size);
+    }
+
+    output.push_str(
+        r#"elsys.fields = fields
+
+function elsys.dissector(buffer, pinfo, tree)
+    pinfo.cols.protocol = "Elsys"
+    local subtree = tree:add(elsys, buffer(), "Elsys uplink")
+    local i = 0
+    while i < buffer:len() do
+        local identifier = buffer(i, 1):uint()
+        local size = sizes[identifier]
+        if size == nil then
+            subtree:add_expert_info(PI_MALFORMED, PI_ERROR,
+                string.format("unknown identifier 0x%02x", identifier))
+            return
+        end
+        if i + 1 + size > buffer:len() then
+            subtree:add_expert_info(PI_MALFORMED, PI_ERROR, "truncated " .. names[identifier])
+            return
+        end
+        local range = buffer(i + 1, size)
+        local item
+        if values[identifier] then
+            item = subtree:add(fields[identifier], range, values[identifier](range))
+        else
+            item = subtree:add(fields[identifier], range)
+        end
+        if units[identifier] then
+            item:append_text(units[identifier])
+        end
+        i = i + 1 + size
+    end
+end
+"#,
+    );
+
+    output
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[path = "./test_dissector.rs"]
+mod test_dissector;
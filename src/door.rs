@@ -0,0 +1,117 @@
+use crate::edges::{Direction, EdgeDetector};
+use crate::Uplink;
+
+/// How a door contact on the external digital input is wired, and how long it may stay open.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DoorConfig {
+    /// The input level while the door is open.
+    pub open_when: bool,
+    pub max_open_s: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Event {
+    Opened {
+        at_s: u64,
+    },
+    /// The door closed at `at_s` after being open for `open_s`.
+    Closed {
+        at_s: u64,
+        open_s: u64,
+    },
+    /// The door has been open since `since_s` for longer than the configured maximum. Raised once
+    /// per opening.
+    OpenTooLong {
+        since_s: u64,
+        open_s: u64,
+    },
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct DoorStats {
+    pub openings: u32,
+    /// Total duration of completed openings.
+    pub open_s: u64,
+    pub longest_open_s: u64,
+    pub too_long: u32,
+}
+
+/// Tracks the door of one device, with openings and closings timed from the uplink in which they
+/// are first seen. A door already open at the first uplink is taken as opened then. Uplinks must be
+/// pushed in chronological order.
+#[derive(Clone, Debug)]
+pub struct DoorTracker {
+    config: DoorConfig,
+    edges: EdgeDetector,
+    opened_s: Option<u64>,
+    alerted: bool,
+    stats: DoorStats,
+}
+
+impl DoorTracker {
+    pub fn new(config: DoorConfig) -> Self {
+        Self {
+            config,
+            edges: EdgeDetector::new(),
+            opened_s: None,
+            alerted: false,
+            stats: DoorStats::default(),
+        }
+    }
+
+    pub fn is_open(&self) -> Option<bool> {
+        self.edges
+            .state()
+            .map(|state| state == self.config.open_when)
+    }
+
+    pub fn stats(&self) -> DoorStats {
+        self.stats
+    }
+
+    /// `frame_counter` is the LoRaWAN uplink frame counter, as for `EdgeDetector::push`.
+    pub fn push(&mut self, timestamp_s: u64, frame_counter: u32, uplink: &Uplink) -> Option<Event> {
+        let first = self.edges.state().is_none();
+        let edge = self.edges.push(timestamp_s, frame_counter, uplink);
+        let opened = match edge {
+            Some(edge) => Some((edge.direction == Direction::Rising) == self.config.open_when),
+            None if first && self.is_open() == Some(true) => Some(true),
+            None => None,
+        };
+
+        match opened {
+            Some(true) => {
+                self.opened_s = Some(timestamp_s);
+                self.alerted = false;
+                self.stats.openings += 1;
+                Some(Event::Opened { at_s: timestamp_s })
+            }
+            Some(false) => {
+                let opened_s = self.opened_s.take()?;
+                let open_s = timestamp_s.saturating_sub(opened_s);
+                self.stats.open_s += open_s;
+                self.stats.longest_open_s = self.stats.longest_open_s.max(open_s);
+                Some(Event::Closed {
+                    at_s: timestamp_s,
+                    open_s,
+                })
+            }
+            None => {
+                let since_s = self.opened_s?;
+                let open_s = timestamp_s.saturating_sub(since_s);
+                if self.alerted || open_s <= self.config.max_open_s {
+                    return None;
+                }
+                self.alerted = true;
+                self.stats.too_long += 1;
+                Some(Event::OpenTooLong { since_s, open_s })
+            }
+        }
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[path = "./test_door.rs"]
+mod test_door;
@@ -0,0 +1,81 @@
+use crate::aggregate::quantile;
+use crate::{Calibration, Field, Uplink};
+
+/// The `q`-quantile of a field over a device's history. A low quantile of CO2 tracks the sensor's
+/// fresh-air baseline; the median suits fields without a natural floor.
+pub fn baseline(readings: &[(u64, Uplink)], field: Field, q: f32) -> Option<f32> {
+    let values = readings
+        .iter()
+        .filter_map(|(_, uplink)| uplink.value(field))
+        .collect::<Vec<_>>();
+    quantile(&values, q)
+}
+
+/// A device baseline which deviates from a reference baseline, either the median of the fleet's
+/// baselines or the same device's baseline over an earlier period.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Drift {
+    pub field: Field,
+    pub baseline: f32,
+    pub reference: f32,
+}
+
+pub fn detect(field: Field, baseline: f32, reference: f32, tolerance: f32) -> Option<Drift> {
+    if (baseline - reference).abs() > tolerance {
+        Some(Drift {
+            field,
+            baseline,
+            reference,
+        })
+    } else {
+        None
+    }
+}
+
+impl Drift {
+    pub fn deviation(&self) -> f32 {
+        self.baseline - self.reference
+    }
+
+    /// The calibration which would bring the baseline back to the reference, for fields which can
+    /// be calibrated. Other fields need the sensor inspected or replaced.
+    pub fn recommended_calibration(&self) -> Option<Calibration> {
+        match self.field {
+            Field::Temperature => Some(Calibration {
+                temperature_offset: -self.deviation(),
+                ..Calibration::default()
+            }),
+            Field::Co2 => Some(Calibration {
+                co2_offset_ppm: -self.deviation(),
+                ..Calibration::default()
+            }),
+            Field::Humidity => Some(Calibration {
+                humidity_offset_percent: -self.deviation(),
+                ..Calibration::default()
+            }),
+            Field::ExternalTemperature1 => Some(Calibration {
+                external_temperature_1_offset: -self.deviation(),
+                ..Calibration::default()
+            }),
+            Field::ExternalTemperature2 => Some(Calibration {
+                external_temperature_2_offset: -self.deviation(),
+                ..Calibration::default()
+            }),
+            Field::Battery
+            | Field::Occupancy
+            | Field::ExternalDigital
+            | Field::Light
+            | Field::Motion
+            | Field::Pulse1
+            | Field::Pulse1Absolute
+            | Field::Distance
+            | Field::AccelerationMotion
+            | Field::Waterleak => None,
+        }
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[path = "./test_drift.rs"]
+mod test_drift;
@@ -0,0 +1,76 @@
+use crate::Uplink;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Direction {
+    Rising,
+    Falling,
+}
+
+/// A change of the external digital input, detected between two uplinks.
+///
+/// The input changed at some point after `previous_s` and no later than `timestamp_s`. When frames
+/// were missed in between, `missed_frames` is non-zero and the input may have toggled more often.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Edge {
+    pub direction: Direction,
+    pub previous_s: u64,
+    pub timestamp_s: u64,
+    pub missed_frames: u32,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Last {
+    timestamp_s: u64,
+    state: bool,
+}
+
+/// Tracks the external digital input of one device across uplinks pushed in chronological order.
+#[derive(Clone, Debug, Default)]
+pub struct EdgeDetector {
+    last: Option<Last>,
+    frame_counter: Option<u32>,
+    missed_frames: u32,
+}
+
+impl EdgeDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn state(&self) -> Option<bool> {
+        self.last.map(|last| last.state)
+    }
+
+    /// `frame_counter` is the LoRaWAN uplink frame counter, used to detect missed frames. A counter
+    /// lower than the previous one is taken as a rejoin or reset, with no frames missed.
+    pub fn push(&mut self, timestamp_s: u64, frame_counter: u32, uplink: &Uplink) -> Option<Edge> {
+        if let Some(previous) = self.frame_counter.replace(frame_counter) {
+            let missed = frame_counter.saturating_sub(previous).saturating_sub(1);
+            self.missed_frames = self.missed_frames.saturating_add(missed);
+        }
+
+        let state = uplink.external_digital()?;
+        let missed_frames = std::mem::take(&mut self.missed_frames);
+        let previous = self.last.replace(Last { timestamp_s, state })?;
+
+        if previous.state == state {
+            return None;
+        }
+
+        Some(Edge {
+            direction: if state {
+                Direction::Rising
+            } else {
+                Direction::Falling
+            },
+            previous_s: previous.timestamp_s,
+            timestamp_s,
+            missed_frames,
+        })
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[path = "./test_edges.rs"]
+mod test_edges;
@@ -0,0 +1,128 @@
+use std::io::{Error, ErrorKind, Result};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::calendar::parse_rfc3339_s;
+use crate::record::Record;
+use crate::stream::decode_hex;
+
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Encoding {
+    Base64,
+    Hex,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeFormat {
+    Rfc3339,
+    UnixSeconds,
+    UnixMilliseconds,
+}
+
+/// Where the parts of an uplink are found in a network server's JSON message, so that a webhook
+/// format can be configured rather than coded.
+///
+/// Selectors are dot-separated paths such as `$.uplink.payload` or `rxInfo.0.time`, where numeric
+/// segments index into arrays and the leading `$.` is optional. A DevEUI is a hex string.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Envelope {
+    pub dev_eui: String,
+    pub payload: String,
+    pub encoding: Encoding,
+    #[serde(default)]
+    pub port: Option<String>,
+    pub timestamp: String,
+    pub time_format: TimeFormat,
+}
+
+/// An uplink extracted from a message, with its LoRaWAN port if the envelope selects one.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Extracted {
+    pub record: Record,
+    pub port: Option<u8>,
+}
+
+impl Envelope {
+    /// Extracts the uplink of one message, or `None` if the message has no payload, as for join
+    /// requests and status messages.
+    pub fn extract(&self, message: &str) -> Result<Option<Extracted>> {
+        let message: Value = serde_json::from_str(message)
+            .map_err(|e| Error::new(ErrorKind::InvalidData, e.to_string()))?;
+
+        let payload = match select(&message, &self.payload) {
+            None | Some(Value::Null) => return Ok(None),
+            Some(Value::String(payload)) => payload,
+            Some(_) => return Err(invalid(&self.payload, "is not a string")),
+        };
+        let payload = match self.encoding {
+            Encoding::Base64 => {
+                base64::decode(payload).map_err(|e| invalid(&self.payload, &e.to_string()))?
+            }
+            Encoding::Hex => decode_hex(payload)?,
+        };
+
+        let dev_eui = select_str(&message, &self.dev_eui)?;
+        let dev_eui = u64::from_str_radix(dev_eui, 16)
+            .map_err(|_| invalid(&self.dev_eui, "is not a DevEUI"))?;
+
+        let timestamp_s = match self.time_format {
+            TimeFormat::Rfc3339 => parse_rfc3339_s(select_str(&message, &self.timestamp)?),
+            TimeFormat::UnixSeconds => select_u64(&message, &self.timestamp)?,
+            TimeFormat::UnixMilliseconds => {
+                select_u64(&message, &self.timestamp)?.map(|ms| ms / 1000)
+            }
+        }
+        .ok_or_else(|| invalid(&self.timestamp, "is not a timestamp"))?;
+
+        let port = match &self.port {
+            Some(selector) => Some(
+                select_u64(&message, selector)?
+                    .and_then(|port| u8::try_from(port).ok())
+                    .ok_or_else(|| invalid(selector, "is not a port"))?,
+            ),
+            None => None,
+        };
+
+        Ok(Some(Extracted {
+            record: Record {
+                timestamp_s,
+                dev_eui,
+                payload,
+            },
+            port,
+        }))
+    }
+}
+
+fn select<'a>(message: &'a Value, selector: &str) -> Option<&'a Value> {
+    let path = selector.strip_prefix("$.").unwrap_or(selector);
+    path.split('.')
+        .try_fold(message, |value, segment| match value {
+            Value::Array(array) => array.get(segment.parse::<usize>().ok()?),
+            _ => value.get(segment),
+        })
+}
+
+fn select_str<'a>(message: &'a Value, selector: &str) -> Result<&'a str> {
+    select(message, selector)
+        .and_then(Value::as_str)
+        .ok_or_else(|| invalid(selector, "is not a string"))
+}
+
+fn select_u64(message: &Value, selector: &str) -> Result<Option<u64>> {
+    select(message, selector)
+        .map(Value::as_u64)
+        .ok_or_else(|| invalid(selector, "is missing"))
+}
+
+fn invalid(selector: &str, message: &str) -> Error {
+    Error::new(ErrorKind::InvalidData, format!("{} {}", selector, message))
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[path = "./test_envelope.rs"]
+mod test_envelope;
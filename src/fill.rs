@@ -0,0 +1,113 @@
+use crate::aggregate::quantile;
+use crate::trend;
+use crate::Uplink;
+
+/// How a distance sensor is mounted above the contents of a bin or tank.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FillConfig {
+    /// The distance to the bottom of the empty container.
+    pub empty_mm: u16,
+    /// The distance to the contents of the full container.
+    pub full_mm: u16,
+    /// A fall in fill level of more than this many percentage points is taken as the container
+    /// being emptied, and predictions start afresh.
+    pub emptied_drop_percent: f32,
+}
+
+/// How the fill level is fitted over time.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Fit {
+    LeastSquares,
+    /// The median of the slopes between all pairs of readings, which is not thrown off by the odd
+    /// reading off a bag or a lid.
+    TheilSen,
+}
+
+impl FillConfig {
+    /// The fill level of a distance reading, from 0 % empty to 100 % full. `None` unless `empty_mm`
+    /// is above `full_mm`.
+    pub fn level_percent(&self, distance_mm: u16) -> Option<f32> {
+        if self.empty_mm <= self.full_mm {
+            return None;
+        }
+        let depth_mm = f32::from(self.empty_mm - self.full_mm);
+        let level = (f32::from(self.empty_mm) - f32::from(distance_mm)) / depth_mm * 100.0;
+        Some(level.clamp(0.0, 100.0))
+    }
+
+    /// The fill levels since the container was last emptied. `readings` must be sorted by
+    /// timestamp.
+    pub fn since_emptied(&self, readings: &[(u64, Uplink)]) -> Vec<(u64, f32)> {
+        let mut output: Vec<(u64, f32)> = Vec::new();
+        for (timestamp_s, uplink) in readings {
+            let Some(level) = uplink.distance_mm().and_then(|d| self.level_percent(d)) else {
+                continue;
+            };
+            if output
+                .last()
+                .is_some_and(|(_, last)| last - level > self.emptied_drop_percent)
+            {
+                output.clear();
+            }
+            output.push((*timestamp_s, level));
+        }
+        output
+    }
+
+    /// Seconds after the latest reading until the fitted fill level reaches `target_percent`, for
+    /// planning collections. 0 if the fitted level is already there, and `None` without two
+    /// readings at different times since the container was last emptied, or if it is not filling.
+    pub fn time_to_fill_s(
+        &self,
+        readings: &[(u64, Uplink)],
+        fit: Fit,
+        target_percent: f32,
+    ) -> Option<u64> {
+        let levels = self.since_emptied(readings);
+        let latest_s = levels.last()?.0;
+        // Hours before the latest reading, so that the intercept is the fitted level now.
+        let points = levels
+            .iter()
+            .map(|(timestamp_s, level)| (-((latest_s - timestamp_s) as f32) / 3600.0, *level))
+            .collect::<Vec<_>>();
+
+        let (slope_per_hour, level) = match fit {
+            Fit::LeastSquares => {
+                let slope_per_hour = trend::fit(levels.iter().copied())?;
+                let n = points.len() as f32;
+                let mean_h = points.iter().map(|(hours, _)| hours).sum::<f32>() / n;
+                let mean_level = points.iter().map(|(_, level)| level).sum::<f32>() / n;
+                (slope_per_hour, mean_level - slope_per_hour * mean_h)
+            }
+            Fit::TheilSen => {
+                let slopes = points
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(i, a)| points.iter().skip(i + 1).map(move |b| (a, b)))
+                    .filter(|((t_a, _), (t_b, _))| t_b > t_a)
+                    .map(|((t_a, v_a), (t_b, v_b))| (v_b - v_a) / (t_b - t_a))
+                    .collect::<Vec<_>>();
+                let slope_per_hour = quantile(&slopes, 0.5)?;
+                let intercepts = points
+                    .iter()
+                    .map(|(hours, level)| level - slope_per_hour * hours)
+                    .collect::<Vec<_>>();
+                (slope_per_hour, quantile(&intercepts, 0.5)?)
+            }
+        };
+
+        if level >= target_percent {
+            return Some(0);
+        }
+        let time_s = (target_percent - level) / slope_per_hour * 3600.0;
+        (time_s.is_finite() && time_s >= 0.0).then(|| time_s.ceil() as u64)
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[path = "./test_fill.rs"]
+mod test_fill;
@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+use std::io::Result;
+
+use crate::aggregate::quantile;
+use crate::{DeviceState, Field, Uplink};
+
+/// The distribution of the latest value of one field across the fleet.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FieldSummary {
+    pub field: Field,
+    pub devices: usize,
+    pub min: f32,
+    pub p10: f32,
+    pub median: f32,
+    pub p90: f32,
+    pub max: f32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Summary {
+    pub devices: usize,
+    /// DevEUIs of devices not heard from within the maximum age, in ascending order.
+    pub silent: Vec<u64>,
+    pub fields: Vec<FieldSummary>,
+}
+
+/// Uplinks received from one device, whether or not they could be deserialized.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Activity {
+    pub first_s: Option<u64>,
+    pub last_s: Option<u64>,
+    pub uplinks: u64,
+    pub errors: u64,
+}
+
+/// The state of many devices, keyed by DevEUI.
+#[derive(Clone, Debug, Default)]
+pub struct Fleet {
+    devices: HashMap<u64, DeviceState>,
+    activity: HashMap<u64, Activity>,
+}
+
+impl Fleet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, dev_eui: u64, timestamp_s: u64, uplink: &Uplink) {
+        self.devices
+            .entry(dev_eui)
+            .or_default()
+            .update(timestamp_s, uplink);
+        self.activity(dev_eui, timestamp_s).uplinks += 1;
+    }
+
+    /// Deserializes an uplink and updates the device, counting failures as decode errors.
+    pub fn deserialize(&mut self, dev_eui: u64, timestamp_s: u64, input: &[u8]) -> Result<Uplink> {
+        match Uplink::deserialize(input) {
+            Ok(uplink) => {
+                self.update(dev_eui, timestamp_s, &uplink);
+                Ok(uplink)
+            }
+            Err(e) => {
+                self.activity(dev_eui, timestamp_s).errors += 1;
+                Err(e)
+            }
+        }
+    }
+
+    fn activity(&mut self, dev_eui: u64, timestamp_s: u64) -> &mut Activity {
+        let activity = self.activity.entry(dev_eui).or_default();
+        activity.first_s = Some(activity.first_s.map_or(timestamp_s, |s| s.min(timestamp_s)));
+        activity.last_s = Some(activity.last_s.map_or(timestamp_s, |s| s.max(timestamp_s)));
+        activity
+    }
+
+    pub fn device(&self, dev_eui: u64) -> Option<&DeviceState> {
+        self.devices.get(&dev_eui)
+    }
+
+    pub fn device_activity(&self, dev_eui: u64) -> Option<&Activity> {
+        self.activity.get(&dev_eui)
+    }
+
+    pub fn devices(&self) -> impl Iterator<Item = (u64, &DeviceState)> {
+        self.devices
+            .iter()
+            .map(|(dev_eui, state)| (*dev_eui, state))
+    }
+
+    pub fn len(&self) -> usize {
+        self.devices.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.devices.is_empty()
+    }
+
+    pub fn silent(&self, now_s: u64, max_age_s: u64) -> Vec<u64> {
+        let mut output = self
+            .devices()
+            .filter(|(_, state)| {
+                state
+                    .last_uplink_s()
+                    .is_none_or(|last_s| now_s.saturating_sub(last_s) > max_age_s)
+            })
+            .map(|(dev_eui, _)| dev_eui)
+            .collect::<Vec<_>>();
+        output.sort_unstable();
+        output
+    }
+
+    /// The latest value of `field` of every device which has reported it.
+    pub fn latest(&self, field: Field) -> Vec<f32> {
+        self.devices
+            .values()
+            .filter_map(|state| state.value(field).map(|t| t.value))
+            .collect()
+    }
+
+    pub fn percentile(&self, field: Field, q: f32) -> Option<f32> {
+        quantile(&self.latest(field), q)
+    }
+
+    /// Counts devices by battery voltage. `edges_v` must be ascending; the output has one more bin
+    /// than there are edges, the first for voltages below `edges_v[0]`.
+    pub fn battery_histogram(&self, edges_v: &[f32]) -> Vec<usize> {
+        let mut output = vec![0; edges_v.len() + 1];
+        for voltage in self.latest(Field::Battery) {
+            output[edges_v.partition_point(|edge| *edge <= voltage)] += 1;
+        }
+        output
+    }
+
+    pub fn summary(&self, now_s: u64, max_age_s: u64) -> Summary {
+        let fields = Field::ALL
+            .iter()
+            .filter_map(|field| {
+                let values = self.latest(*field);
+                Some(FieldSummary {
+                    field: *field,
+                    devices: values.len(),
+                    min: quantile(&values, 0.0)?,
+                    p10: quantile(&values, 0.1)?,
+                    median: quantile(&values, 0.5)?,
+                    p90: quantile(&values, 0.9)?,
+                    max: quantile(&values, 1.0)?,
+                })
+            })
+            .collect();
+
+        Summary {
+            devices: self.len(),
+            silent: self.silent(now_s, max_age_s),
+            fields,
+        }
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[path = "./test_fleet.rs"]
+mod test_fleet;
@@ -0,0 +1,83 @@
+use crate::{Field, Uplink};
+
+/// Forecasts one field of one device with Holt's linear exponential smoothing. With `beta` of 0
+/// the trend is never learnt and it reduces to an exponentially weighted moving average.
+///
+/// `alpha` and `beta`, between 0 and 1, weigh each new reading against the smoothed level and
+/// trend. Uplinks must be pushed in chronological order.
+#[derive(Clone, Debug)]
+pub struct Forecaster {
+    field: Field,
+    alpha: f32,
+    beta: f32,
+    state: Option<State>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct State {
+    timestamp_s: u64,
+    level: f32,
+    trend_per_s: f32,
+}
+
+impl Forecaster {
+    pub fn new(field: Field, alpha: f32, beta: f32) -> Self {
+        Self {
+            field,
+            alpha,
+            beta,
+            state: None,
+        }
+    }
+
+    pub fn push(&mut self, timestamp_s: u64, uplink: &Uplink) {
+        let Some(value) = uplink.value(self.field) else {
+            return;
+        };
+
+        self.state = Some(match self.state {
+            None => State {
+                timestamp_s,
+                level: value,
+                trend_per_s: 0.0,
+            },
+            Some(state) if timestamp_s <= state.timestamp_s => state,
+            Some(state) => {
+                let elapsed_s = (timestamp_s - state.timestamp_s) as f32;
+                let predicted = state.level + state.trend_per_s * elapsed_s;
+                let level = self.alpha * value + (1.0 - self.alpha) * predicted;
+                let trend_per_s = self.beta * (level - state.level) / elapsed_s
+                    + (1.0 - self.beta) * state.trend_per_s;
+                State {
+                    timestamp_s,
+                    level,
+                    trend_per_s,
+                }
+            }
+        });
+    }
+
+    /// The smoothed trend, in the units of the field's accessor per hour.
+    pub fn trend_per_hour(&self) -> Option<f32> {
+        self.state.map(|state| state.trend_per_s * 3600.0)
+    }
+
+    /// The expected value `horizon_s` seconds after the latest reading.
+    pub fn forecast(&self, horizon_s: u64) -> Option<f32> {
+        self.state
+            .map(|state| state.level + state.trend_per_s * horizon_s as f32)
+    }
+
+    /// Seconds after the latest reading until the forecast reaches `threshold`, or `None` if the
+    /// trend is heading away from it.
+    pub fn time_to_threshold_s(&self, threshold: f32) -> Option<u64> {
+        let state = self.state?;
+        let time_s = (threshold - state.level) / state.trend_per_s;
+        (time_s.is_finite() && time_s >= 0.0).then(|| time_s.ceil() as u64)
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[path = "./test_forecast.rs"]
+mod test_forecast;
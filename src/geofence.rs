@@ -0,0 +1,92 @@
+use crate::{GpsPosition, Uplink};
+
+/// Mean radius of the Earth, in metres.
+const EARTH_RADIUS_M: f64 = 6_371_000.0;
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Geofence {
+    Circle {
+        centre: GpsPosition,
+        radius_m: f64,
+    },
+    /// Vertices in order, closed from the last back to the first. Edges are straight in latitude
+    /// and longitude, so polygons should be small and not cross the antimeridian.
+    Polygon(Vec<GpsPosition>),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Event {
+    Entered { at_s: u64 },
+    Exited { at_s: u64 },
+}
+
+impl GpsPosition {
+    /// Great-circle distance by the haversine formula.
+    pub fn distance_m(&self, other: &GpsPosition) -> f64 {
+        let (latitude_1, latitude_2) = (self.latitude.to_radians(), other.latitude.to_radians());
+        let half_latitude = (latitude_2 - latitude_1) / 2.0;
+        let half_longitude = (other.longitude - self.longitude).to_radians() / 2.0;
+        let a = half_latitude.sin().powi(2)
+            + latitude_1.cos() * latitude_2.cos() * half_longitude.sin().powi(2);
+        2.0 * EARTH_RADIUS_M * a.sqrt().min(1.0).asin()
+    }
+
+    pub fn inside(&self, geofence: &Geofence) -> bool {
+        match geofence {
+            Geofence::Circle { centre, radius_m } => self.distance_m(centre) <= *radius_m,
+            Geofence::Polygon(vertices) => {
+                let mut inside = false;
+                let edges = vertices.iter().zip(vertices.iter().cycle().skip(1));
+                for (a, b) in edges {
+                    if (a.latitude > self.latitude) != (b.latitude > self.latitude) {
+                        let crossing = a.longitude
+                            + (self.latitude - a.latitude) / (b.latitude - a.latitude)
+                                * (b.longitude - a.longitude);
+                        if self.longitude < crossing {
+                            inside = !inside;
+                        }
+                    }
+                }
+                inside
+            }
+        }
+    }
+}
+
+/// Detects one device entering and leaving a geofence. A device inside at its first position is
+/// taken as entering then. Uplinks without a position are ignored.
+#[derive(Clone, Debug)]
+pub struct GeofenceTracker {
+    geofence: Geofence,
+    inside: Option<bool>,
+}
+
+impl GeofenceTracker {
+    pub fn new(geofence: Geofence) -> Self {
+        Self {
+            geofence,
+            inside: None,
+        }
+    }
+
+    pub fn inside(&self) -> Option<bool> {
+        self.inside
+    }
+
+    pub fn push(&mut self, timestamp_s: u64, uplink: &Uplink) -> Option<Event> {
+        let inside = uplink.gps()?.inside(&self.geofence);
+        let previous = self.inside.replace(inside);
+        match (previous, inside) {
+            (None | Some(false), true) => Some(Event::Entered { at_s: timestamp_s }),
+            (Some(true), false) => Some(Event::Exited { at_s: timestamp_s }),
+            _ => None,
+        }
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[path = "./test_geofence.rs"]
+mod test_geofence;
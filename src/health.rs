@@ -0,0 +1,87 @@
+use crate::fleet::{Activity, Fleet};
+
+/// Relative weights of the health components. A component which cannot be assessed, such as the
+/// battery of a device which does not report it, is left out and the others reweighted.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Weights {
+    pub battery: f32,
+    pub regularity: f32,
+    pub errors: f32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HealthConfig {
+    pub weights: Weights,
+    /// Battery voltage scoring 0.
+    pub battery_empty_v: f32,
+    /// Battery voltage scoring full marks. The battery is not assessed unless this is above
+    /// `battery_empty_v`.
+    pub battery_full_v: f32,
+    /// The device's configured send period.
+    pub expected_interval_s: u64,
+}
+
+impl Default for HealthConfig {
+    fn default() -> Self {
+        Self {
+            weights: Weights {
+                battery: 1.0,
+                regularity: 1.0,
+                errors: 1.0,
+            },
+            battery_empty_v: 3.0,
+            battery_full_v: 3.6,
+            expected_interval_s: 600,
+        }
+    }
+}
+
+/// A health score from 0 (failing) to 100 (healthy).
+pub fn score(
+    config: &HealthConfig,
+    battery_v: Option<f32>,
+    activity: &Activity,
+    now_s: u64,
+) -> Option<u8> {
+    let range_v = config.battery_full_v - config.battery_empty_v;
+    let battery = battery_v
+        .filter(|_| range_v > 0.0)
+        .map(|v| ((v - config.battery_empty_v) / range_v).clamp(0.0, 1.0));
+
+    let received = activity.uplinks + activity.errors;
+    let regularity = activity.first_s.map(|first_s| {
+        let expected = now_s.saturating_sub(first_s) / config.expected_interval_s.max(1) + 1;
+        (received as f32 / expected as f32).min(1.0)
+    });
+
+    let errors = (received > 0).then(|| 1.0 - activity.errors as f32 / received as f32);
+
+    let weighted = [
+        (battery, config.weights.battery),
+        (regularity, config.weights.regularity),
+        (errors, config.weights.errors),
+    ]
+    .iter()
+    .filter_map(|(component, weight)| component.map(|c| (c * weight, *weight)))
+    .fold((0.0, 0.0), |(sum, total), (c, w)| (sum + c, total + w));
+
+    match weighted {
+        (_, total) if total <= 0.0 => None,
+        (sum, total) => Some((100.0 * sum / total).round() as u8),
+    }
+}
+
+impl Fleet {
+    pub fn health(&self, dev_eui: u64, config: &HealthConfig, now_s: u64) -> Option<u8> {
+        let battery_v = self
+            .device(dev_eui)
+            .and_then(|state| state.battery_voltage())
+            .map(|b| b.value);
+        score(config, battery_v, self.device_activity(dev_eui)?, now_s)
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[path = "./test_health.rs"]
+mod test_health;
@@ -0,0 +1,68 @@
+use std::fmt::Write;
+
+use crate::calendar::format_rfc3339_s;
+use crate::snapshot::render;
+use crate::Uplink;
+
+/// Renders the positions of one device as a KML document for Google Earth: the track as a line,
+/// and a placemark at each position, timestamped and described with the uplink's decoded values.
+/// `readings` must be sorted by timestamp, and those without a position are left out.
+pub fn track(name: &str, readings: &[(u64, Uplink)]) -> String {
+    let positions = readings
+        .iter()
+        .filter_map(|(timestamp_s, uplink)| Some((*timestamp_s, uplink.gps()?, uplink)))
+        .collect::<Vec<_>>();
+
+    let mut output = String::new();
+    let _ = writeln!(output, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>");
+    let _ = writeln!(output, "<kml xmlns=\"http://www.opengis.net/kml/2.2\">");
+    let _ = writeln!(output, "<Document>");
+    let _ = writeln!(output, "<name>{}</name>", escape(name));
+
+    let _ = writeln!(output, "<Placemark>");
+    let _ = writeln!(output, "<name>{}</name>", escape(name));
+    let _ = writeln!(output, "<LineString>");
+    let _ = writeln!(output, "<coordinates>");
+    for (_, position, _) in &positions {
+        let _ = writeln!(output, "{:.4},{:.4}", position.longitude, position.latitude);
+    }
+    let _ = writeln!(output, "</coordinates>");
+    let _ = writeln!(output, "</LineString>");
+    let _ = writeln!(output, "</Placemark>");
+
+    for (timestamp_s, position, uplink) in &positions {
+        let _ = writeln!(output, "<Placemark>");
+        let _ = writeln!(
+            output,
+            "<TimeStamp><when>{}</when></TimeStamp>",
+            format_rfc3339_s(*timestamp_s)
+        );
+        let _ = writeln!(
+            output,
+            "<description>{}</description>",
+            escape(render(uplink).trim_end())
+        );
+        let _ = writeln!(
+            output,
+            "<Point><coordinates>{:.4},{:.4}</coordinates></Point>",
+            position.longitude, position.latitude
+        );
+        let _ = writeln!(output, "</Placemark>");
+    }
+
+    let _ = writeln!(output, "</Document>");
+    let _ = writeln!(output, "</kml>");
+    output
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[path = "./test_kml.rs"]
+mod test_kml;
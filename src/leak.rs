@@ -0,0 +1,86 @@
+use crate::Uplink;
+
+/// When a water-leak reading raises an alarm.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LeakConfig {
+    /// Readings above this are a leak.
+    pub threshold: u8,
+    /// After an acknowledgement, leak readings raise no new alarm for this long, so that a floor
+    /// still drying out does not raise one with every uplink.
+    pub suppress_s: u64,
+}
+
+impl Default for LeakConfig {
+    fn default() -> Self {
+        Self {
+            threshold: 0,
+            suppress_s: 3600,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Event {
+    Latched {
+        at_s: u64,
+    },
+    /// An alarm latched at `since_s` was acknowledged at `at_s`.
+    Acknowledged {
+        since_s: u64,
+        at_s: u64,
+    },
+}
+
+/// A water-leak alarm for one device. A leak reading latches the alarm, which stays raised whether
+/// or not later readings are dry, until it is acknowledged. Uplinks must be pushed in
+/// chronological order.
+#[derive(Clone, Debug)]
+pub struct LeakAlarm {
+    config: LeakConfig,
+    latched_s: Option<u64>,
+    acknowledged_s: Option<u64>,
+}
+
+impl LeakAlarm {
+    pub fn new(config: LeakConfig) -> Self {
+        Self {
+            config,
+            latched_s: None,
+            acknowledged_s: None,
+        }
+    }
+
+    /// When the alarm latched, while it is raised.
+    pub fn latched(&self) -> Option<u64> {
+        self.latched_s
+    }
+
+    pub fn push(&mut self, timestamp_s: u64, uplink: &Uplink) -> Option<Event> {
+        let leak = uplink.waterleak()? > self.config.threshold;
+        let suppressed = self
+            .acknowledged_s
+            .is_some_and(|at_s| timestamp_s < at_s.saturating_add(self.config.suppress_s));
+        if !leak || suppressed || self.latched_s.is_some() {
+            return None;
+        }
+
+        self.latched_s = Some(timestamp_s);
+        Some(Event::Latched { at_s: timestamp_s })
+    }
+
+    /// Clears the alarm, if raised.
+    pub fn acknowledge(&mut self, timestamp_s: u64) -> Option<Event> {
+        let since_s = self.latched_s.take()?;
+        self.acknowledged_s = Some(timestamp_s);
+        Some(Event::Acknowledged {
+            since_s,
+            at_s: timestamp_s,
+        })
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[path = "./test_leak.rs"]
+mod test_leak;
@@ -1,2 +1,69 @@
+pub mod aggregate;
+pub mod airtime;
+pub mod anomaly;
+#[cfg(feature = "zstd")]
+pub mod archive;
+#[cfg(feature = "tokio")]
+pub mod async_stream;
+#[cfg(feature = "bacnet")]
+pub mod bacnet;
+#[cfg(feature = "redb")]
+pub mod buffer;
+pub mod cache;
+pub mod calendar;
+pub mod calibration;
+pub mod condensation;
+#[cfg(feature = "toml")]
+pub mod config;
+pub mod consistency;
+pub mod dedup;
+pub mod degree_days;
+pub mod device_state;
+pub mod dissector;
+pub mod door;
+pub mod drift;
+pub mod edges;
+#[cfg(feature = "json")]
+pub mod envelope;
+pub mod fill;
+pub mod fleet;
+pub mod forecast;
+pub mod geofence;
+pub mod health;
+pub mod kml;
+pub mod leak;
+pub mod lifetime;
+pub mod limits;
+pub mod metrics;
+#[cfg(feature = "modbus")]
+pub mod modbus;
+pub mod mold;
+pub mod occupants;
+pub mod presence;
+pub mod pulse;
+pub mod push;
+pub mod radio;
+pub mod record;
+pub mod region;
+pub mod replay;
+pub mod resample;
+pub mod rules;
+pub mod smooth;
+pub mod snapshot;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+#[cfg(feature = "statsd")]
+pub mod statsd;
+pub mod stream;
+pub mod tlv;
+pub mod tracking;
+pub mod trend;
+#[cfg(feature = "ttn")]
+pub mod ttn;
 pub mod uplink;
-pub use uplink::{Occupancy, Uplink};
+pub mod utilization;
+pub mod ventilation;
+pub use calibration::Calibration;
+pub use device_state::{DeviceState, Timestamped};
+pub use limits::{Limits, LimitsExceeded};
+pub use uplink::{Acceleration, ErrorDetail, Field, GpsPosition, Occupancy, Uplink};
@@ -0,0 +1,63 @@
+use crate::airtime::{uplink_airtime_us, Modulation};
+use crate::uplink::record_size;
+use crate::Field;
+
+/// The current a device draws, which depends on the model and is best measured.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Consumption {
+    pub sleep_ua: f64,
+    pub transmit_ma: f64,
+    /// Charge drawn taking one sample of a field, in microampere seconds.
+    pub sample_uas: Vec<(Field, f64)>,
+}
+
+/// How a device is configured to sample and send.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Profile {
+    pub sample_period_s: u64,
+    pub send_period_s: u64,
+    pub fields: Vec<Field>,
+    pub modulation: Modulation,
+}
+
+impl Profile {
+    /// Length of the application payload sending every field once.
+    pub fn payload_len(&self) -> usize {
+        self.fields
+            .iter()
+            .filter_map(|field| record_size(field.identifier()))
+            .map(|size| size + 1)
+            .sum()
+    }
+}
+
+/// The average current drawn, in microamperes. `None` if the modulation is invalid.
+pub fn average_current_ua(consumption: &Consumption, profile: &Profile) -> Option<f64> {
+    let airtime_s = uplink_airtime_us(&profile.modulation, profile.payload_len())? as f64 * 1e-6;
+    let transmit_ua = consumption.transmit_ma * 1000.0 * airtime_s / profile.send_period_s as f64;
+
+    let sample_uas: f64 = consumption
+        .sample_uas
+        .iter()
+        .filter(|(field, _)| profile.fields.contains(field))
+        .map(|(_, uas)| uas)
+        .sum();
+    let sample_ua = sample_uas / profile.sample_period_s as f64;
+
+    Some(consumption.sleep_ua + transmit_ua + sample_ua)
+}
+
+/// Expected battery life, in seconds, of a battery of `capacity_mah`.
+pub fn battery_life_s(
+    capacity_mah: f64,
+    consumption: &Consumption,
+    profile: &Profile,
+) -> Option<u64> {
+    Some((capacity_mah * 1000.0 * 3600.0 / average_current_ua(consumption, profile)?) as u64)
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[path = "./test_lifetime.rs"]
+mod test_lifetime;
@@ -0,0 +1,100 @@
+use std::error;
+use std::fmt;
+use std::io::{Error, ErrorKind, Result};
+
+use crate::{tlv, Uplink};
+
+/// Bounds on the work done decoding a single payload.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Limits {
+    pub max_payload_bytes: usize,
+    pub max_records: usize,
+    /// How often any one identifier may appear in a payload.
+    pub max_repeats: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            max_payload_bytes: 242,
+            max_records: 64,
+            max_repeats: 4,
+        }
+    }
+}
+
+/// Returned inside an `io::Error` of kind `InvalidData` when a payload exceeds its `Limits`, so
+/// that callers can tell it apart from a malformed payload with `Error::get_ref` and
+/// `downcast_ref`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LimitsExceeded {
+    PayloadBytes { limit: usize, actual: usize },
+    Records { limit: usize },
+    Repeats { identifier: u8, limit: usize },
+}
+
+impl fmt::Display for LimitsExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::PayloadBytes { limit, actual } => {
+                write!(
+                    f,
+                    "payload is {} bytes, over the limit of {}",
+                    actual, limit
+                )
+            }
+            Self::Records { limit } => write!(f, "payload has more than {} records", limit),
+            Self::Repeats { identifier, limit } => write!(
+                f,
+                "identifier 0x{:02x} appears more than {} times",
+                identifier, limit
+            ),
+        }
+    }
+}
+
+impl error::Error for LimitsExceeded {}
+
+impl Uplink {
+    /// As `deserialize`, but first checks the payload against `limits` without decoding anything.
+    pub fn deserialize_with_limits(input: &[u8], limits: &Limits) -> Result<Self> {
+        check(input, limits).map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+        Self::deserialize(input)
+    }
+}
+
+fn check(input: &[u8], limits: &Limits) -> std::result::Result<(), LimitsExceeded> {
+    if input.len() > limits.max_payload_bytes {
+        return Err(LimitsExceeded::PayloadBytes {
+            limit: limits.max_payload_bytes,
+            actual: input.len(),
+        });
+    }
+
+    let mut repeats = [0; 256];
+    // A malformed payload ends the scan; `deserialize` reports it.
+    for (records, (identifier, _)) in tlv::records(input).map_while(Result::ok).enumerate() {
+        if records >= limits.max_records {
+            return Err(LimitsExceeded::Records {
+                limit: limits.max_records,
+            });
+        }
+
+        repeats[identifier as usize] += 1;
+        if repeats[identifier as usize] > limits.max_repeats {
+            return Err(LimitsExceeded::Repeats {
+                identifier,
+                limit: limits.max_repeats,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[path = "./test_limits.rs"]
+mod test_limits;
@@ -0,0 +1,94 @@
+use std::io::Result;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+
+use crate::{tlv, Uplink};
+
+/// Upper bounds of the latency histogram buckets, in nanoseconds. A final bucket counts slower
+/// decodes.
+pub const LATENCY_BUCKETS_NS: [u64; 8] = [
+    1_000, 2_000, 5_000, 10_000, 20_000, 50_000, 100_000, 1_000_000,
+];
+
+/// Decode counters, safe to share between threads. Deserialize through `Metrics::deserialize` to
+/// record them, and take a `Snapshot` to export them to a metrics system.
+pub struct Metrics {
+    successes: AtomicU64,
+    failures: AtomicU64,
+    identifiers: [AtomicU64; 256],
+    latency: [AtomicU64; LATENCY_BUCKETS_NS.len() + 1],
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Snapshot {
+    pub successes: u64,
+    pub failures: u64,
+    /// Occurrences of each identifier in successfully deserialized payloads, by identifier.
+    pub identifiers: Vec<(u8, u64)>,
+    /// Decodes per latency bucket, matching `LATENCY_BUCKETS_NS` with one more bucket at the end.
+    pub latency: Vec<u64>,
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self {
+            successes: AtomicU64::new(0),
+            failures: AtomicU64::new(0),
+            identifiers: [const { AtomicU64::new(0) }; 256],
+            latency: [const { AtomicU64::new(0) }; LATENCY_BUCKETS_NS.len() + 1],
+        }
+    }
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn deserialize(&self, input: &[u8]) -> Result<Uplink> {
+        let start = Instant::now();
+        let output = Uplink::deserialize(input);
+        let elapsed_ns = start.elapsed().as_nanos();
+
+        let bucket = LATENCY_BUCKETS_NS.partition_point(|bound| (*bound as u128) < elapsed_ns);
+        self.latency[bucket].fetch_add(1, Ordering::Relaxed);
+
+        match &output {
+            Ok(_) => {
+                self.successes.fetch_add(1, Ordering::Relaxed);
+                for (identifier, _) in tlv::records(input).flatten() {
+                    self.identifiers[identifier as usize].fetch_add(1, Ordering::Relaxed);
+                }
+            }
+            Err(_) => {
+                self.failures.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        output
+    }
+
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            successes: self.successes.load(Ordering::Relaxed),
+            failures: self.failures.load(Ordering::Relaxed),
+            identifiers: self
+                .identifiers
+                .iter()
+                .enumerate()
+                .map(|(identifier, count)| (identifier as u8, count.load(Ordering::Relaxed)))
+                .filter(|(_, count)| *count > 0)
+                .collect(),
+            latency: self
+                .latency
+                .iter()
+                .map(|count| count.load(Ordering::Relaxed))
+                .collect(),
+        }
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[path = "./test_metrics.rs"]
+mod test_metrics;
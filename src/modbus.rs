@@ -0,0 +1,191 @@
+use std::collections::BTreeMap;
+use std::io::{Error, ErrorKind, Read, Result, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use crate::{Field, Uplink};
+
+/// The register value of a signed 16-bit field which has not been received or is out of range.
+pub const NO_VALUE: u16 = 0x8000;
+
+const READ_HOLDING_REGISTERS: u8 = 0x03;
+const READ_INPUT_REGISTERS: u8 = 0x04;
+const ILLEGAL_FUNCTION: u8 = 0x01;
+const ILLEGAL_DATA_ADDRESS: u8 = 0x02;
+const ILLEGAL_DATA_VALUE: u8 = 0x03;
+const MAX_QUANTITY: u16 = 125;
+
+/// How a scaled value is held in registers. A field which has not been received, or whose scaled
+/// value is out of range, reads as the encoding's no-value marker, which is never a valid value.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Encoding {
+    /// -32767 to 32767, with `NO_VALUE` (-32768) as the marker.
+    #[default]
+    I16,
+    /// 0 to 65534, with 0xffff as the marker.
+    U16,
+    /// 0 to 0xfffffffe across two registers, high word first, with 0xffffffff as the marker.
+    U32,
+}
+
+impl Encoding {
+    fn no_value(self) -> Vec<u16> {
+        match self {
+            Encoding::I16 => vec![NO_VALUE],
+            Encoding::U16 => vec![u16::MAX],
+            Encoding::U32 => vec![u16::MAX, u16::MAX],
+        }
+    }
+
+    fn encode(self, scaled: f64) -> Vec<u16> {
+        match self {
+            Encoding::I16 if (-32767.0..=32767.0).contains(&scaled) => vec![scaled as i16 as u16],
+            Encoding::U16 if (0.0..u16::MAX as f64).contains(&scaled) => vec![scaled as u16],
+            Encoding::U32 if (0.0..u32::MAX as f64).contains(&scaled) => {
+                let value = scaled as u32;
+                vec![(value >> 16) as u16, value as u16]
+            }
+            _ => self.no_value(),
+        }
+    }
+}
+
+/// One field of one device, held as `value * scale` in one or two registers from `address`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Register {
+    pub dev_eui: u64,
+    pub field: Field,
+    pub address: u16,
+    pub scale: f32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub encoding: Encoding,
+}
+
+/// Registers kept up to date from decoded uplinks, served the same as both holding and input
+/// registers.
+#[derive(Clone, Debug)]
+pub struct RegisterMap {
+    registers: Vec<Register>,
+    values: BTreeMap<u16, u16>,
+}
+
+impl RegisterMap {
+    pub fn new(registers: &[Register]) -> Result<Self> {
+        let mut values = BTreeMap::new();
+        for register in registers {
+            for (offset, value) in register.encoding.no_value().into_iter().enumerate() {
+                let Some(address) = register.address.checked_add(offset as u16) else {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        format!("register {} runs past the last address", register.address),
+                    ));
+                };
+                if values.insert(address, value).is_some() {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        format!("register {} is mapped twice", address),
+                    ));
+                }
+            }
+        }
+
+        Ok(Self {
+            registers: registers.to_vec(),
+            values,
+        })
+    }
+
+    pub fn update(&mut self, dev_eui: u64, uplink: &Uplink) {
+        for register in self.registers.iter().filter(|r| r.dev_eui == dev_eui) {
+            if let Some(value) = uplink.value_f64(register.field) {
+                let scaled = (value * register.scale as f64).round();
+                for (address, word) in (register.address..).zip(register.encoding.encode(scaled)) {
+                    self.values.insert(address, word);
+                }
+            }
+        }
+    }
+
+    /// Reads `count` consecutive registers, or `None` if any of them is not mapped.
+    pub fn read(&self, address: u16, count: u16) -> Option<Vec<u16>> {
+        (0..count)
+            .map(|offset| {
+                let address = address.checked_add(offset)?;
+                self.values.get(&address).copied()
+            })
+            .collect()
+    }
+
+    /// Answers one Modbus TCP request, or returns `None` for a frame which is not one.
+    pub fn handle(&self, request: &[u8]) -> Option<Vec<u8>> {
+        let [t0, t1, 0, 0, l0, l1, unit, function, pdu @ ..] = request else {
+            return None;
+        };
+        if u16::from_be_bytes([*l0, *l1]) as usize != 2 + pdu.len() {
+            return None;
+        }
+
+        let pdu = match (*function, pdu) {
+            (READ_HOLDING_REGISTERS | READ_INPUT_REGISTERS, [a0, a1, q0, q1]) => {
+                let address = u16::from_be_bytes([*a0, *a1]);
+                let quantity = u16::from_be_bytes([*q0, *q1]);
+                if !(1..=MAX_QUANTITY).contains(&quantity) {
+                    vec![function | 0x80, ILLEGAL_DATA_VALUE]
+                } else if let Some(values) = self.read(address, quantity) {
+                    let mut pdu = vec![*function, (quantity * 2) as u8];
+                    pdu.extend(values.iter().flat_map(|value| value.to_be_bytes()));
+                    pdu
+                } else {
+                    vec![function | 0x80, ILLEGAL_DATA_ADDRESS]
+                }
+            }
+            (READ_HOLDING_REGISTERS | READ_INPUT_REGISTERS, _) => {
+                vec![function | 0x80, ILLEGAL_DATA_VALUE]
+            }
+            _ => vec![function | 0x80, ILLEGAL_FUNCTION],
+        };
+
+        let mut response = vec![*t0, *t1, 0, 0];
+        response.extend(((1 + pdu.len()) as u16).to_be_bytes());
+        response.push(*unit);
+        response.extend(pdu);
+        Some(response)
+    }
+}
+
+/// Serves the register map over Modbus TCP, one thread per connection, until accepting fails.
+pub fn serve(listener: TcpListener, map: Arc<Mutex<RegisterMap>>) -> Result<()> {
+    for stream in listener.incoming() {
+        let stream = stream?;
+        let map = Arc::clone(&map);
+        thread::spawn(move || serve_connection(stream, &map));
+    }
+    Ok(())
+}
+
+fn serve_connection(mut stream: TcpStream, map: &Mutex<RegisterMap>) -> Result<()> {
+    loop {
+        let mut request = vec![0; 6];
+        stream.read_exact(&mut request)?;
+        let length = u16::from_be_bytes([request[4], request[5]]) as usize;
+        request.resize(6 + length, 0);
+        stream.read_exact(&mut request[6..])?;
+
+        let response = map
+            .lock()
+            .map_err(|_| Error::other("register map lock poisoned"))?
+            .handle(&request);
+        match response {
+            Some(response) => stream.write_all(&response)?,
+            None => return Err(Error::new(ErrorKind::InvalidData, "not a Modbus TCP frame")),
+        }
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[path = "./test_modbus.rs"]
+mod test_modbus;
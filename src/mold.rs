@@ -0,0 +1,72 @@
+use crate::Uplink;
+
+/// The lowest relative humidity at which mold can grow at `temperature` (°C), from the isopleth of
+/// the VTT model (Hukka and Viitanen, 1999). `None` outside 0 to 50 °C, where mold does not grow.
+pub fn critical_humidity(temperature: f32) -> Option<f32> {
+    match temperature {
+        t if !(0.0..=50.0).contains(&t) => None,
+        t if t <= 20.0 => Some(-0.00267 * t.powi(3) + 0.160 * t.powi(2) - 3.13 * t + 100.0),
+        _ => Some(80.0),
+    }
+}
+
+/// How long conditions favoured mold growth.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct MoldRisk {
+    /// Time at or above the critical humidity.
+    pub favourable_s: u64,
+    /// The longest unbroken stretch at or above the critical humidity, as the risk grows with
+    /// sustained exposure rather than brief peaks.
+    pub longest_favourable_s: u64,
+    pub observed_s: u64,
+}
+
+impl MoldRisk {
+    pub fn ratio(&self) -> Option<f32> {
+        if self.observed_s == 0 {
+            None
+        } else {
+            Some(self.favourable_s as f32 / self.observed_s as f32)
+        }
+    }
+}
+
+/// Mold risk over a device's history. `readings` must be sorted by timestamp. Each reading with
+/// both temperature and humidity holds until the next such reading, for at most `max_gap_s`, so
+/// that outages are not counted.
+pub fn mold_risk(readings: &[(u64, Uplink)], max_gap_s: u64) -> MoldRisk {
+    let conditions = readings
+        .iter()
+        .filter_map(|(timestamp_s, uplink)| {
+            let humidity = f32::from(uplink.humidity_percent()?);
+            let favourable = critical_humidity(uplink.temperature()?)
+                .is_some_and(|critical| humidity >= critical);
+            Some((*timestamp_s, favourable))
+        })
+        .collect::<Vec<_>>();
+
+    let mut output = MoldRisk::default();
+    let mut stretch_s = 0;
+    for pair in conditions.windows(2) {
+        let [(from_s, favourable), (to_s, _)] = pair else {
+            continue;
+        };
+        let held_s = to_s.saturating_sub(*from_s).min(max_gap_s);
+        output.observed_s += held_s;
+        if *favourable {
+            output.favourable_s += held_s;
+            stretch_s += held_s;
+            output.longest_favourable_s = output.longest_favourable_s.max(stretch_s);
+        }
+        if !*favourable || held_s < to_s - from_s {
+            stretch_s = 0;
+        }
+    }
+
+    output
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[path = "./test_mold.rs"]
+mod test_mold;
@@ -0,0 +1,67 @@
+use crate::trend::Tracker;
+use crate::{Field, Uplink};
+
+/// What the CO2 mass balance of a room needs to know.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Room {
+    pub volume_m3: f32,
+    pub air_changes_per_hour: f32,
+    pub outdoor_ppm: f32,
+    /// CO2 exhaled per occupant.
+    pub generation_m3_per_hour: f32,
+}
+
+impl Room {
+    /// A room with 420 ppm outside and occupants doing office work, who exhale about 18 litres of
+    /// CO2 an hour each.
+    pub fn new(volume_m3: f32, air_changes_per_hour: f32) -> Self {
+        Self {
+            volume_m3,
+            air_changes_per_hour,
+            outdoor_ppm: 420.0,
+            generation_m3_per_hour: 0.018,
+        }
+    }
+
+    /// Occupants from `V dC/dt = N G - ACH V (C - outdoor)`, given the CO2 concentration and its
+    /// rate of change. Never negative.
+    pub fn occupants(&self, co2_ppm: f32, ppm_per_hour: f32) -> f32 {
+        let removed = self.air_changes_per_hour * (co2_ppm - self.outdoor_ppm);
+        let m3_per_hour = self.volume_m3 * (ppm_per_hour + removed) * 1e-6;
+        (m3_per_hour / self.generation_m3_per_hour).max(0.0)
+    }
+
+    /// Occupants once the CO2 concentration has settled.
+    pub fn steady_state_occupants(&self, co2_ppm: f32) -> f32 {
+        self.occupants(co2_ppm, 0.0)
+    }
+}
+
+/// Estimates the occupants of a room from the CO2 of one device, using its rate of change over a
+/// sliding window. Uplinks must be pushed in chronological order.
+#[derive(Clone, Debug)]
+pub struct Estimator {
+    room: Room,
+    tracker: Tracker,
+}
+
+impl Estimator {
+    pub fn new(room: Room, window_s: u64) -> Self {
+        Self {
+            room,
+            tracker: Tracker::new(Field::Co2, window_s, 0.0),
+        }
+    }
+
+    pub fn push(&mut self, timestamp_s: u64, uplink: &Uplink) -> Option<f32> {
+        let trend = self.tracker.push(timestamp_s, uplink)?;
+        let co2_ppm = uplink.co2_ppm()?;
+        Some(self.room.occupants(co2_ppm as f32, trend.slope_per_hour))
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[path = "./test_occupants.rs"]
+mod test_occupants;
@@ -0,0 +1,146 @@
+use crate::{Occupancy, Uplink};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Presence {
+    Occupied,
+    Vacant,
+}
+
+/// How long a new presence must persist before it is reported, in seconds.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Debounce {
+    pub occupied_after_s: u64,
+    pub vacant_after_s: u64,
+}
+
+/// A stable change of presence, which began at `since_s` and was confirmed at `confirmed_s`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Change {
+    pub presence: Presence,
+    pub since_s: u64,
+    pub confirmed_s: u64,
+}
+
+/// Smooths the occupancy of one device into stable occupied/vacant presence.
+///
+/// `Occupancy::PendingOrPir` is treated as no evidence either way, so flapping between pending and
+/// occupied does not produce transitions. Uplinks must be pushed in chronological order.
+#[derive(Clone, Debug)]
+pub struct Debouncer {
+    debounce: Debounce,
+    stable: Option<Presence>,
+    candidate: Option<(Presence, u64)>,
+}
+
+impl Debouncer {
+    pub fn new(debounce: Debounce) -> Self {
+        Self {
+            debounce,
+            stable: None,
+            candidate: None,
+        }
+    }
+
+    pub fn presence(&self) -> Option<Presence> {
+        self.stable
+    }
+
+    pub fn push(&mut self, timestamp_s: u64, uplink: &Uplink) -> Option<Change> {
+        let presence = match uplink.occupancy()? {
+            Occupancy::OccupiedOrHeat => Presence::Occupied,
+            Occupancy::NoBody => Presence::Vacant,
+            Occupancy::PendingOrPir => return self.confirm(timestamp_s),
+        };
+
+        if Some(presence) == self.stable {
+            self.candidate = None;
+            return None;
+        }
+
+        match self.candidate {
+            Some((candidate, _)) if candidate == presence => {}
+            _ => self.candidate = Some((presence, timestamp_s)),
+        }
+
+        self.confirm(timestamp_s)
+    }
+
+    fn confirm(&mut self, timestamp_s: u64) -> Option<Change> {
+        let (presence, since_s) = self.candidate?;
+        let dwell_s = match presence {
+            Presence::Occupied => self.debounce.occupied_after_s,
+            Presence::Vacant => self.debounce.vacant_after_s,
+        };
+
+        if timestamp_s.saturating_sub(since_s) < dwell_s {
+            return None;
+        }
+
+        self.stable = Some(presence);
+        self.candidate = None;
+        Some(Change {
+            presence,
+            since_s,
+            confirmed_s: timestamp_s,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Event {
+    Entered {
+        at_s: u64,
+    },
+    /// The room was vacated at `at_s` after being occupied for `occupied_s`.
+    Vacated {
+        at_s: u64,
+        occupied_s: u64,
+    },
+}
+
+/// Turns the presence of one device into entered and vacated events, timed from when each
+/// presence began rather than when it was confirmed. A room first seen vacant produces no event.
+#[derive(Clone, Debug)]
+pub struct Transitions {
+    debouncer: Debouncer,
+    entered_s: Option<u64>,
+}
+
+impl Transitions {
+    pub fn new(debounce: Debounce) -> Self {
+        Self {
+            debouncer: Debouncer::new(debounce),
+            entered_s: None,
+        }
+    }
+
+    pub fn push(&mut self, timestamp_s: u64, uplink: &Uplink) -> Option<Event> {
+        let change = self.debouncer.push(timestamp_s, uplink)?;
+        match change.presence {
+            Presence::Occupied => {
+                self.entered_s = Some(change.since_s);
+                Some(Event::Entered {
+                    at_s: change.since_s,
+                })
+            }
+            Presence::Vacant => self.entered_s.take().map(|entered_s| Event::Vacated {
+                at_s: change.since_s,
+                occupied_s: change.since_s.saturating_sub(entered_s),
+            }),
+        }
+    }
+}
+
+/// The events of readings in chronological order.
+pub fn events(readings: &[(u64, Uplink)], debounce: Debounce) -> Vec<Event> {
+    let mut transitions = Transitions::new(debounce);
+    readings
+        .iter()
+        .filter_map(|(timestamp_s, uplink)| transitions.push(*timestamp_s, uplink))
+        .collect()
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[path = "./test_presence.rs"]
+mod test_presence;
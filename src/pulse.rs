@@ -0,0 +1,83 @@
+use crate::utilization::HOUR_S;
+use crate::Uplink;
+
+/// An absolute count which falls from at least this far into the `u32` range has rolled over;
+/// one which falls from lower down was reset, such as by a restart.
+const ROLLOVER_FROM: u32 = u32::MAX - u32::MAX / 4;
+
+/// The pulses counted between two readings.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Rate {
+    pub start_s: u64,
+    pub end_s: u64,
+    pub pulses: u64,
+}
+
+impl Rate {
+    pub fn per_hour(&self) -> f64 {
+        self.pulses as f64 * HOUR_S as f64 / (self.end_s - self.start_s) as f64
+    }
+
+    /// The flow per minute, such as litres per minute for a meter giving `per_pulse` litres per
+    /// pulse.
+    pub fn flow_per_minute(&self, per_pulse: f64) -> f64 {
+        self.per_hour() * per_pulse / 60.0
+    }
+}
+
+/// Rates between successive readings of the absolute pulse count. `readings` must be sorted by
+/// timestamp.
+///
+/// A count lower than the previous one has rolled over if the previous one was in the top quarter
+/// of the counter's range, and was otherwise reset to zero, so that the pulses since the reset are
+/// counted. Readings at the same time as the previous one are skipped.
+pub fn absolute_rates(readings: &[(u64, Uplink)]) -> Vec<Rate> {
+    let counts = readings
+        .iter()
+        .filter_map(|(timestamp_s, uplink)| Some((*timestamp_s, uplink.pulse1_absolute()?)));
+    rates(counts, |previous, count| {
+        match count.checked_sub(previous) {
+            Some(pulses) => pulses,
+            None if previous >= ROLLOVER_FROM => count.wrapping_sub(previous),
+            None => count,
+        }
+    })
+}
+
+/// Rates from the relative pulse count, which counts the pulses since the previous uplink. The
+/// first reading only starts the first interval. `readings` must be sorted by timestamp.
+pub fn relative_rates(readings: &[(u64, Uplink)]) -> Vec<Rate> {
+    let counts = readings
+        .iter()
+        .filter_map(|(timestamp_s, uplink)| Some((*timestamp_s, uplink.pulse1()?)));
+    rates(counts, |_, count| count.into())
+}
+
+fn rates<T: Copy, C, F>(counts: C, pulses: F) -> Vec<Rate>
+where
+    C: Iterator<Item = (u64, T)>,
+    F: Fn(T, T) -> u32,
+{
+    let mut output = Vec::new();
+    let mut previous: Option<(u64, T)> = None;
+
+    for (timestamp_s, count) in counts {
+        match previous {
+            Some((previous_s, _)) if timestamp_s <= previous_s => continue,
+            Some((previous_s, previous_count)) => output.push(Rate {
+                start_s: previous_s,
+                end_s: timestamp_s,
+                pulses: pulses(previous_count, count).into(),
+            }),
+            None => {}
+        }
+        previous = Some((timestamp_s, count));
+    }
+
+    output
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[path = "./test_pulse.rs"]
+mod test_pulse;
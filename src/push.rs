@@ -0,0 +1,106 @@
+#![deny(
+    clippy::indexing_slicing,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic
+)]
+
+use std::io::{Error, ErrorKind, Result};
+
+use crate::uplink::{decode_record, record_size};
+use crate::{ErrorDetail, Uplink};
+
+/// Parses a payload handed over in chunks of any size, decoding each record as soon as its last
+/// byte arrives. The caller marks the end of each payload with `finish`.
+#[derive(Clone, Debug, Default)]
+pub struct PushParser {
+    pending: Vec<u8>,
+    offset: usize,
+    uplink: Uplink,
+    detail: ErrorDetail,
+    /// The first error of the current payload, kept to be returned again by `finish`.
+    error: Option<(ErrorKind, String)>,
+}
+
+impl PushParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A parser quoting payloads in errors only as far as `detail` allows.
+    pub fn with_error_detail(detail: ErrorDetail) -> Self {
+        Self {
+            detail,
+            ..Self::default()
+        }
+    }
+
+    /// Decodes the records completed by `chunk`, returning how many there were. After an error the
+    /// rest of the payload is ignored until `finish`.
+    pub fn feed(&mut self, chunk: &[u8]) -> Result<usize> {
+        let mut completed = 0;
+        for byte in chunk {
+            if self.error.is_some() {
+                break;
+            }
+            let identifier = self.pending.first().copied().unwrap_or(*byte);
+            self.pending.push(*byte);
+            self.offset += 1;
+
+            let Some(size) = record_size(identifier) else {
+                return Err(self.fail(Error::new(
+                    ErrorKind::InvalidData,
+                    format!(
+                        "index {} has value {}, which is not an identifier",
+                        self.offset - 1,
+                        identifier
+                    ),
+                )));
+            };
+            if self.pending.len() == size + 1 {
+                if let Err(fault) = decode_record(&self.pending, &mut self.uplink) {
+                    let error = fault.into_error(&self.pending, self.detail);
+                    return Err(self.fail(error));
+                }
+                self.pending.clear();
+                completed += 1;
+            }
+        }
+        Ok(completed)
+    }
+
+    /// What has been decoded of the current payload so far.
+    pub fn uplink(&self) -> &Uplink {
+        &self.uplink
+    }
+
+    /// Ends the current payload, returning it unless it failed or ended part way through a record,
+    /// and readies the parser for the next one. A payload which failed returns its first error.
+    pub fn finish(&mut self) -> Result<Uplink> {
+        let parser = std::mem::replace(self, Self::with_error_detail(self.detail));
+        if let Some((kind, message)) = parser.error {
+            return Err(Error::new(kind, message));
+        }
+        if !parser.pending.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "payload ends part way through a record at index {}",
+                    parser.offset - parser.pending.len()
+                ),
+            ));
+        }
+        Ok(parser.uplink)
+    }
+
+    fn fail(&mut self, error: Error) -> Error {
+        self.error = Some((error.kind(), error.to_string()));
+        error
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[allow(clippy::indexing_slicing, clippy::unwrap_used)]
+#[path = "./test_push.rs"]
+mod test_push;
@@ -0,0 +1,68 @@
+use std::cmp::Ordering;
+
+use crate::Uplink;
+
+/// One gateway's reception of a frame.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Reception {
+    pub gateway_id: u64,
+    pub rssi_dbm: i16,
+    pub snr_db: f32,
+}
+
+/// Every gateway's reception of a single frame, as reported by the network server.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RadioMeta {
+    pub receptions: Vec<Reception>,
+}
+
+/// A decoded uplink together with how it was received.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Received {
+    pub uplink: Uplink,
+    pub radio: RadioMeta,
+}
+
+impl RadioMeta {
+    /// The reception with the highest SNR, and of those the highest RSSI.
+    pub fn best(&self) -> Option<&Reception> {
+        self.receptions.iter().max_by(|a, b| quality(a, b))
+    }
+
+    pub fn best_gateway(&self) -> Option<u64> {
+        self.best().map(|reception| reception.gateway_id)
+    }
+
+    /// Adds the receptions of the same frame reported separately, keeping the better reception
+    /// where a gateway is reported twice.
+    pub fn merge(&mut self, other: &RadioMeta) {
+        for reception in &other.receptions {
+            match self
+                .receptions
+                .iter_mut()
+                .find(|r| r.gateway_id == reception.gateway_id)
+            {
+                Some(existing) => {
+                    if quality(reception, existing) == Ordering::Greater {
+                        *existing = *reception;
+                    }
+                }
+                None => self.receptions.push(*reception),
+            }
+        }
+    }
+}
+
+/// Orders receptions by SNR, then RSSI, with NaN SNRs ordered as by `f32::total_cmp`.
+fn quality(a: &Reception, b: &Reception) -> Ordering {
+    a.snr_db
+        .total_cmp(&b.snr_db)
+        .then(a.rssi_dbm.cmp(&b.rssi_dbm))
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[path = "./test_radio.rs"]
+mod test_radio;
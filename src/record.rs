@@ -0,0 +1,114 @@
+// Records are read from archives and logs which may be truncated or corrupt, so reading them must
+// not panic whatever the input.
+#![deny(
+    clippy::indexing_slicing,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic
+)]
+
+use std::io::{Error, ErrorKind, Read, Result, Write};
+
+use crate::Uplink;
+
+/// A raw uplink as received: seconds since the Unix epoch, DevEUI and payload.
+///
+/// The encoding is big-endian `timestamp_s: u64`, `dev_eui: u64`, `payload length: u16`, followed
+/// by the payload itself.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Record {
+    pub timestamp_s: u64,
+    pub dev_eui: u64,
+    pub payload: Vec<u8>,
+}
+
+const HEADER_SIZE: usize = 8 + 8 + 2;
+
+impl Record {
+    pub fn uplink(&self) -> Result<Uplink> {
+        Uplink::deserialize(&self.payload)
+    }
+
+    pub fn encoded_len(&self) -> usize {
+        HEADER_SIZE + self.payload.len()
+    }
+
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> Result<()> {
+        let length = u16::try_from(self.payload.len()).map_err(|_| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                format!("payload of {} bytes is too long", self.payload.len()),
+            )
+        })?;
+
+        let mut header = Vec::with_capacity(HEADER_SIZE);
+        header.extend_from_slice(&self.timestamp_s.to_be_bytes());
+        header.extend_from_slice(&self.dev_eui.to_be_bytes());
+        header.extend_from_slice(&length.to_be_bytes());
+        writer.write_all(&header)?;
+        writer.write_all(&self.payload)
+    }
+
+    /// Reads one record, or `None` at a clean end of input.
+    pub fn read_from<R: Read>(reader: &mut R) -> Result<Option<Self>> {
+        let mut timestamp_s = [0; 8];
+        if !read_unless_eof(reader, &mut timestamp_s)? {
+            return Ok(None);
+        }
+        let mut dev_eui = [0; 8];
+        reader.read_exact(&mut dev_eui)?;
+        let mut length = [0; 2];
+        reader.read_exact(&mut length)?;
+
+        let timestamp_s = u64::from_be_bytes(timestamp_s);
+        let dev_eui = u64::from_be_bytes(dev_eui);
+        let length = u16::from_be_bytes(length);
+
+        let mut payload = vec![0; length as usize];
+        reader.read_exact(&mut payload)?;
+
+        Ok(Some(Self {
+            timestamp_s,
+            dev_eui,
+            payload,
+        }))
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let mut output = Vec::with_capacity(self.encoded_len());
+        self.write_to(&mut output)?;
+        Ok(output)
+    }
+
+    pub fn from_bytes(mut input: &[u8]) -> Result<Self> {
+        let output = Self::read_from(&mut input)?.ok_or(ErrorKind::UnexpectedEof)?;
+        if !input.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("{} bytes left over after record", input.len()),
+            ));
+        }
+        Ok(output)
+    }
+}
+
+/// Fills `buffer`, or returns `false` if the input ends before the first byte.
+pub(crate) fn read_unless_eof<R: Read>(reader: &mut R, buffer: &mut [u8]) -> Result<bool> {
+    let mut filled = 0;
+    while let Some(remaining) = buffer.get_mut(filled..).filter(|r| !r.is_empty()) {
+        match reader.read(remaining) {
+            Ok(0) if filled == 0 => return Ok(false),
+            Ok(0) => return Err(Error::from(ErrorKind::UnexpectedEof)),
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(true)
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[allow(clippy::indexing_slicing, clippy::unwrap_used)]
+#[path = "./test_record.rs"]
+mod test_record;
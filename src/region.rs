@@ -0,0 +1,151 @@
+use std::io::{Error, ErrorKind, Result};
+
+use crate::airtime::{uplink_airtime_us, DutyCycle, Modulation, SubBand};
+
+/// LoRaWAN regional parameters, as in RP002-1.0.3. AS923 assumes the uplink dwell time limit is
+/// off.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Region {
+    Eu868,
+    Us915,
+    As923,
+}
+
+struct DataRate {
+    spreading_factor: u8,
+    bandwidth_hz: u32,
+    max_payload: usize,
+}
+
+#[rustfmt::skip]
+const EU868_DATA_RATES: &[DataRate] = &[
+    DataRate { spreading_factor: 12, bandwidth_hz: 125_000, max_payload: 51 },
+    DataRate { spreading_factor: 11, bandwidth_hz: 125_000, max_payload: 51 },
+    DataRate { spreading_factor: 10, bandwidth_hz: 125_000, max_payload: 51 },
+    DataRate { spreading_factor: 9, bandwidth_hz: 125_000, max_payload: 115 },
+    DataRate { spreading_factor: 8, bandwidth_hz: 125_000, max_payload: 222 },
+    DataRate { spreading_factor: 7, bandwidth_hz: 125_000, max_payload: 222 },
+    DataRate { spreading_factor: 7, bandwidth_hz: 250_000, max_payload: 222 },
+];
+
+/// With the uplink dwell time limit off.
+#[rustfmt::skip]
+const AS923_DATA_RATES: &[DataRate] = &[
+    DataRate { spreading_factor: 12, bandwidth_hz: 125_000, max_payload: 51 },
+    DataRate { spreading_factor: 11, bandwidth_hz: 125_000, max_payload: 51 },
+    DataRate { spreading_factor: 10, bandwidth_hz: 125_000, max_payload: 115 },
+    DataRate { spreading_factor: 9, bandwidth_hz: 125_000, max_payload: 115 },
+    DataRate { spreading_factor: 8, bandwidth_hz: 125_000, max_payload: 242 },
+    DataRate { spreading_factor: 7, bandwidth_hz: 125_000, max_payload: 242 },
+    DataRate { spreading_factor: 7, bandwidth_hz: 250_000, max_payload: 242 },
+];
+
+#[rustfmt::skip]
+const US915_DATA_RATES: &[DataRate] = &[
+    DataRate { spreading_factor: 10, bandwidth_hz: 125_000, max_payload: 11 },
+    DataRate { spreading_factor: 9, bandwidth_hz: 125_000, max_payload: 53 },
+    DataRate { spreading_factor: 8, bandwidth_hz: 125_000, max_payload: 125 },
+    DataRate { spreading_factor: 7, bandwidth_hz: 125_000, max_payload: 242 },
+    DataRate { spreading_factor: 8, bandwidth_hz: 500_000, max_payload: 242 },
+];
+
+/// The EU868 sub-bands of ETSI EN 300 220.
+#[rustfmt::skip]
+const EU868_SUB_BANDS: &[SubBand] = &[
+    SubBand { min_hz: 863_000_000, max_hz: 865_000_000, duty_cycle: 0.001 },
+    SubBand { min_hz: 865_000_000, max_hz: 868_000_000, duty_cycle: 0.01 },
+    SubBand { min_hz: 868_000_000, max_hz: 868_600_000, duty_cycle: 0.01 },
+    SubBand { min_hz: 868_700_000, max_hz: 869_200_000, duty_cycle: 0.001 },
+    SubBand { min_hz: 869_400_000, max_hz: 869_650_000, duty_cycle: 0.1 },
+    SubBand { min_hz: 869_700_000, max_hz: 870_000_000, duty_cycle: 0.01 },
+];
+
+#[rustfmt::skip]
+const AS923_SUB_BANDS: &[SubBand] = &[
+    SubBand { min_hz: 915_000_000, max_hz: 928_000_000, duty_cycle: 0.01 },
+];
+
+const DUTY_CYCLE_WINDOW_S: u64 = 3600;
+
+impl Region {
+    fn data_rates(&self) -> &'static [DataRate] {
+        match self {
+            Self::Eu868 => EU868_DATA_RATES,
+            Self::As923 => AS923_DATA_RATES,
+            Self::Us915 => US915_DATA_RATES,
+        }
+    }
+
+    /// The uplink modulation of a data rate. FSK data rates are not covered.
+    pub fn modulation(&self, data_rate: u8) -> Option<Modulation> {
+        self.data_rates()
+            .get(data_rate as usize)
+            .map(|dr| Modulation::lorawan(dr.spreading_factor, dr.bandwidth_hz))
+    }
+
+    /// The largest application payload at a data rate, without MAC commands in FOpts.
+    pub fn max_payload(&self, data_rate: u8) -> Option<usize> {
+        self.data_rates()
+            .get(data_rate as usize)
+            .map(|dr| dr.max_payload)
+    }
+
+    /// The sub-bands with a duty-cycle limit. US915 limits dwell time instead.
+    pub fn sub_bands(&self) -> &'static [SubBand] {
+        match self {
+            Self::Eu868 => EU868_SUB_BANDS,
+            Self::Us915 => &[],
+            Self::As923 => AS923_SUB_BANDS,
+        }
+    }
+
+    /// The longest a single uplink may be on air, where the region limits it.
+    pub fn max_dwell_us(&self) -> Option<u64> {
+        match self {
+            Self::Us915 => Some(400_000),
+            Self::Eu868 | Self::As923 => None,
+        }
+    }
+
+    /// Duty-cycle accounting over the hour the regulations measure it in.
+    pub fn duty_cycle(&self) -> DutyCycle {
+        DutyCycle::new(self.sub_bands(), DUTY_CYCLE_WINDOW_S)
+    }
+
+    /// Checks that an application payload of `length` bytes can be sent at a data rate.
+    pub fn validate_uplink(&self, data_rate: u8, length: usize) -> Result<()> {
+        let invalid = |message: String| Err(Error::new(ErrorKind::InvalidInput, message));
+
+        let (Some(modulation), Some(max_payload)) =
+            (self.modulation(data_rate), self.max_payload(data_rate))
+        else {
+            return invalid(format!("{:?} has no data rate {}", self, data_rate));
+        };
+        if length > max_payload {
+            return invalid(format!(
+                "{} bytes exceeds the {} byte limit of {:?} DR{}",
+                length, max_payload, self, data_rate
+            ));
+        }
+        let Some(airtime_us) = uplink_airtime_us(&modulation, length) else {
+            return invalid(format!(
+                "{:?} DR{} has an invalid spreading factor",
+                self, data_rate
+            ));
+        };
+        if let Some(max_dwell_us) = self.max_dwell_us().filter(|max| airtime_us > *max) {
+            return invalid(format!(
+                "{} us on air exceeds the {} us dwell time of {:?}",
+                airtime_us, max_dwell_us, self
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[path = "./test_region.rs"]
+mod test_region;
@@ -0,0 +1,73 @@
+use std::io::{Error, ErrorKind, Result};
+use std::thread;
+use std::time::Duration;
+
+use crate::record::Record;
+
+/// Re-emits records, such as those of an `archive::Reader`, with the gaps between their timestamps.
+pub struct Replay<I> {
+    records: I,
+    speed: f64,
+    previous_s: Option<u64>,
+}
+
+impl<I: Iterator<Item = Result<Record>>> Replay<I> {
+    pub fn new(records: I) -> Self {
+        Self {
+            records,
+            speed: 1.0,
+            previous_s: None,
+        }
+    }
+
+    /// Replays `speed` times faster than the original; `speed` must be positive and finite.
+    /// Records out of chronological order follow their predecessor without delay, and do not shift
+    /// the timing of later records.
+    pub fn with_speed(records: I, speed: f64) -> Result<Self> {
+        if !(speed > 0.0 && speed.is_finite()) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("replay speed {} is not positive and finite", speed),
+            ));
+        }
+        Ok(Self {
+            speed,
+            ..Self::new(records)
+        })
+    }
+
+    /// Passes each record to `emit` at its time, stopping at the first error.
+    pub fn run<F: FnMut(Record) -> Result<()>>(self, mut emit: F) -> Result<()> {
+        for item in self {
+            let (delay, record) = item?;
+            thread::sleep(delay);
+            emit(record)?;
+        }
+        Ok(())
+    }
+}
+
+impl<I: Iterator<Item = Result<Record>>> Iterator for Replay<I> {
+    /// A record and how long to wait after the previous one before emitting it.
+    type Item = Result<(Duration, Record)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let record = match self.records.next()? {
+            Ok(record) => record,
+            Err(e) => return Some(Err(e)),
+        };
+
+        let gap_s = self.previous_s.map_or(0, |previous_s| {
+            record.timestamp_s.saturating_sub(previous_s)
+        });
+        self.previous_s = self.previous_s.max(Some(record.timestamp_s));
+
+        let delay = Duration::try_from_secs_f64(gap_s as f64 / self.speed).unwrap_or(Duration::MAX);
+        Some(Ok((delay, record)))
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[path = "./test_replay.rs"]
+mod test_replay;
@@ -0,0 +1,157 @@
+use crate::{Field, Occupancy, Uplink};
+
+/// Grid points are `start_s`, `start_s + step_s`, ... up to but excluding `end_s`. A grid point is
+/// only filled from readings at most `max_gap_s` seconds away, so outages stay visible as gaps. A
+/// grid with a `step_s` of zero has no points.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Grid {
+    pub start_s: u64,
+    pub end_s: u64,
+    pub step_s: u64,
+    pub max_gap_s: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Fill {
+    Previous,
+    Linear,
+}
+
+/// Resamples timestamped uplinks onto `grid`.
+///
+/// `fill` applies to measurements such as temperature, CO2 and battery. Occupancy, external digital
+/// and water leak are states, so they are always filled with the previous value and never
+/// interpolated, and so is the absolute pulse count, a running total. The motion, pulse and
+/// acceleration motion counts cover the interval before each uplink, so each is added to the grid
+/// point at or before its uplink and never filled.
+pub fn resample(readings: &[(u64, Uplink)], grid: &Grid, fill: Fill) -> Vec<(u64, Uplink)> {
+    if grid.step_s == 0 {
+        return Vec::new();
+    }
+
+    let mut sorted = readings.iter().collect::<Vec<_>>();
+    sorted.sort_by_key(|(timestamp_s, _)| *timestamp_s);
+
+    let mut output = (grid.start_s..grid.end_s)
+        .step_by(grid.step_s as usize)
+        .map(|timestamp_s| (timestamp_s, Uplink::default()))
+        .collect::<Vec<_>>();
+
+    for field in Field::ALL {
+        let samples = sorted
+            .iter()
+            .filter_map(|(timestamp_s, uplink)| uplink.value_f64(*field).map(|v| (*timestamp_s, v)))
+            .collect::<Vec<_>>();
+
+        let fill = match kind(*field) {
+            Kind::Measurement => fill,
+            Kind::State | Kind::Total => Fill::Previous,
+            Kind::Count => {
+                for (timestamp_s, count) in samples {
+                    if let Some(index) = point(grid, timestamp_s) {
+                        let uplink = &mut output[index].1;
+                        let total = uplink.value_f64(*field).unwrap_or(0.0) + count;
+                        set(uplink, *field, total);
+                    }
+                }
+                continue;
+            }
+        };
+
+        for (timestamp_s, uplink) in output.iter_mut() {
+            if let Some(value) = sample(&samples, *timestamp_s, grid.max_gap_s, fill) {
+                set(uplink, *field, value);
+            }
+        }
+    }
+
+    output
+}
+
+enum Kind {
+    Measurement,
+    State,
+    Total,
+    Count,
+}
+
+fn kind(field: Field) -> Kind {
+    match field {
+        Field::Temperature
+        | Field::Co2
+        | Field::Battery
+        | Field::Humidity
+        | Field::Light
+        | Field::ExternalTemperature1
+        | Field::ExternalTemperature2
+        | Field::Distance => Kind::Measurement,
+        Field::Occupancy | Field::ExternalDigital | Field::Waterleak => Kind::State,
+        Field::Pulse1Absolute => Kind::Total,
+        Field::Motion | Field::Pulse1 | Field::AccelerationMotion => Kind::Count,
+    }
+}
+
+/// The index of the grid point at or before `timestamp_s`.
+fn point(grid: &Grid, timestamp_s: u64) -> Option<usize> {
+    if timestamp_s < grid.start_s || timestamp_s >= grid.end_s {
+        return None;
+    }
+    Some(((timestamp_s - grid.start_s) / grid.step_s) as usize)
+}
+
+fn sample(samples: &[(u64, f64)], timestamp_s: u64, max_gap_s: u64, fill: Fill) -> Option<f64> {
+    let next = samples.partition_point(|(t, _)| *t < timestamp_s);
+    if let Some((t, value)) = samples.get(next) {
+        if *t == timestamp_s {
+            return Some(*value);
+        }
+    }
+
+    let (previous_s, previous) = *samples.get(next.checked_sub(1)?)?;
+    match fill {
+        Fill::Previous => (timestamp_s - previous_s <= max_gap_s).then_some(previous),
+        Fill::Linear => {
+            let (next_s, next) = *samples.get(next)?;
+            if next_s - previous_s > max_gap_s {
+                return None;
+            }
+            let fraction = (timestamp_s - previous_s) as f64 / (next_s - previous_s) as f64;
+            Some(previous + (next - previous) * fraction)
+        }
+    }
+}
+
+fn set(uplink: &mut Uplink, field: Field, value: f64) {
+    match field {
+        Field::Temperature => uplink.temperature = Some(value as f32),
+        Field::Co2 => uplink.co2 = Some(value.round() as u16),
+        Field::Battery => uplink.battery_mv = Some((value * 1000.0).round() as u16),
+        Field::Occupancy => {
+            uplink.occupancy = Some(match value as u8 {
+                0 => Occupancy::NoBody,
+                1 => Occupancy::PendingOrPir,
+                _ => Occupancy::OccupiedOrHeat,
+            })
+        }
+        Field::ExternalDigital => uplink.external_digital = Some(value != 0.0),
+        Field::Humidity => uplink.humidity = Some(value.round().clamp(0.0, 255.0) as u8),
+        Field::Light => uplink.light = Some(value.round().clamp(0.0, u16::MAX as f64) as u16),
+        Field::Motion => uplink.motion = Some(value.round().clamp(0.0, 255.0) as u8),
+        Field::Pulse1 => uplink.pulse1 = Some(value.round().clamp(0.0, u16::MAX as f64) as u16),
+        Field::Pulse1Absolute => {
+            uplink.pulse1_absolute = Some(value.round().clamp(0.0, u32::MAX as f64) as u32)
+        }
+        Field::ExternalTemperature1 => uplink.external_temperature_1 = Some(value as f32),
+        Field::ExternalTemperature2 => uplink.external_temperature_2 = Some(value as f32),
+        Field::Distance => uplink.distance = Some(value.round().clamp(0.0, u16::MAX as f64) as u16),
+        Field::AccelerationMotion => {
+            uplink.acceleration_motion = Some(value.round().clamp(0.0, 255.0) as u8)
+        }
+        Field::Waterleak => uplink.waterleak = Some(value.round().clamp(0.0, 255.0) as u8),
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[path = "./test_resample.rs"]
+mod test_resample;
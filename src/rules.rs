@@ -0,0 +1,225 @@
+#[cfg(feature = "toml")]
+use std::io::{Error, ErrorKind, Result};
+
+use crate::condensation::Derived;
+use crate::{Field, Uplink};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Operator {
+    Above,
+    Below,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Severity {
+    Info,
+    Warning,
+    Critical,
+}
+
+/// What a rule compares: a field, or a quantity derived from several fields. In TOML either is
+/// named by `field`, as in `field = "co2"` or `field = "dew_point_margin"`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
+pub enum Quantity {
+    Field(Field),
+    Derived(Derived),
+}
+
+impl Quantity {
+    pub fn value(self, uplink: &Uplink) -> Option<f32> {
+        match self {
+            Quantity::Field(field) => uplink.value(field),
+            Quantity::Derived(derived) => derived.value(uplink),
+        }
+    }
+}
+
+impl From<Field> for Quantity {
+    fn from(field: Field) -> Self {
+        Quantity::Field(field)
+    }
+}
+
+impl From<Derived> for Quantity {
+    fn from(derived: Derived) -> Self {
+        Quantity::Derived(derived)
+    }
+}
+
+/// An alarm on one quantity, compared in the units of the field's accessor, or in °C for the dew
+/// point quantities. Surfaces approaching condensation are caught by `dew_point_margin` below a
+/// small margin, and freezing by `external_temperature_1` below 0.
+///
+/// The alarm is raised once the condition has held for `dwell_s` seconds, and cleared once the
+/// value is back across the threshold by more than `hysteresis`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rule {
+    pub name: String,
+    pub field: Quantity,
+    pub operator: Operator,
+    pub threshold: f32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub hysteresis: f32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub dwell_s: u64,
+    pub severity: Severity,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Transition {
+    Raised,
+    Cleared,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Event {
+    pub rule: String,
+    pub severity: Severity,
+    pub transition: Transition,
+    pub timestamp_s: u64,
+    pub value: f32,
+}
+
+#[cfg(feature = "toml")]
+#[derive(serde::Deserialize)]
+struct Config {
+    #[serde(default)]
+    rule: Vec<Rule>,
+}
+
+#[derive(Clone, Debug, Default)]
+struct State {
+    breached_since_s: Option<u64>,
+    raised: bool,
+}
+
+/// Evaluates rules against the uplinks of one device. Uplinks must be pushed in chronological
+/// order.
+#[derive(Clone, Debug, Default)]
+pub struct Engine {
+    rules: Vec<(Rule, State)>,
+}
+
+impl Rule {
+    fn breached(&self, value: f32) -> bool {
+        match self.operator {
+            Operator::Above => value > self.threshold,
+            Operator::Below => value < self.threshold,
+        }
+    }
+
+    fn recovered(&self, value: f32) -> bool {
+        match self.operator {
+            Operator::Above => value < self.threshold - self.hysteresis,
+            Operator::Below => value > self.threshold + self.hysteresis,
+        }
+    }
+}
+
+impl Engine {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds an engine from a TOML rule set, one `[[rule]]` table per rule:
+    ///
+    /// ```toml
+    /// [[rule]]
+    /// name = "co2 high"
+    /// field = "co2"
+    /// operator = "above"
+    /// threshold = 1000.0
+    /// hysteresis = 100.0 # optional, default 0
+    /// dwell_s = 600      # optional, default 0
+    /// severity = "warning"
+    /// ```
+    ///
+    /// YAML is not supported. With the `serde` feature, rules read from YAML by the caller can be
+    /// passed to `add`.
+    #[cfg(feature = "toml")]
+    pub fn from_toml(input: &str) -> Result<Self> {
+        let config: Config = toml::from_str(input).map_err(|e| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("not a valid rule set: {}", e),
+            )
+        })?;
+
+        let mut output = Self::new();
+        for rule in config.rule {
+            output.add(rule);
+        }
+        Ok(output)
+    }
+
+    pub fn add(&mut self, rule: Rule) {
+        self.rules.push((rule, State::default()));
+    }
+
+    pub fn rules(&self) -> impl Iterator<Item = &Rule> {
+        self.rules.iter().map(|(rule, _)| rule)
+    }
+
+    pub fn raised(&self) -> impl Iterator<Item = &Rule> {
+        self.rules
+            .iter()
+            .filter(|(_, state)| state.raised)
+            .map(|(rule, _)| rule)
+    }
+
+    pub fn push(&mut self, timestamp_s: u64, uplink: &Uplink) -> Vec<Event> {
+        let mut output = Vec::new();
+
+        for (rule, state) in self.rules.iter_mut() {
+            let value = match rule.field.value(uplink) {
+                Some(value) => value,
+                None => continue,
+            };
+
+            let transition = if state.raised {
+                if rule.recovered(value) {
+                    state.raised = false;
+                    state.breached_since_s = None;
+                    Some(Transition::Cleared)
+                } else {
+                    None
+                }
+            } else if rule.breached(value) {
+                let since_s = *state.breached_since_s.get_or_insert(timestamp_s);
+                if timestamp_s.saturating_sub(since_s) >= rule.dwell_s {
+                    state.raised = true;
+                    Some(Transition::Raised)
+                } else {
+                    None
+                }
+            } else {
+                state.breached_since_s = None;
+                None
+            };
+
+            if let Some(transition) = transition {
+                output.push(Event {
+                    rule: rule.name.clone(),
+                    severity: rule.severity,
+                    transition,
+                    timestamp_s,
+                    value,
+                });
+            }
+        }
+
+        output
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[path = "./test_rules.rs"]
+mod test_rules;
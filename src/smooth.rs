@@ -0,0 +1,94 @@
+use std::collections::VecDeque;
+
+use crate::aggregate::quantile;
+use crate::{Field, Uplink};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Smoothing {
+    /// Number of recent readings the median is taken over.
+    pub window: usize,
+    /// Readings more than this many median absolute deviations from the median of the readings
+    /// before them are outliers, and are left out of the window.
+    pub max_deviations: Option<f32>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Smoothed {
+    pub value: f32,
+    pub outlier: bool,
+}
+
+/// Median filter over one field of one device, with optional outlier rejection, for spiky readings.
+#[derive(Clone, Debug)]
+pub struct Smoother {
+    field: Field,
+    smoothing: Smoothing,
+    history: VecDeque<f32>,
+    /// Consecutive outliers; as many as half a window are taken as a genuine step change.
+    outliers: Vec<f32>,
+}
+
+impl Smoother {
+    pub fn new(field: Field, smoothing: Smoothing) -> Self {
+        Self {
+            field,
+            smoothing,
+            history: VecDeque::new(),
+            outliers: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, uplink: &Uplink) -> Option<Smoothed> {
+        let value = uplink.value(self.field)?;
+
+        let outlier = self.is_outlier(value);
+        if outlier {
+            self.outliers.push(value);
+            if self.outliers.len() * 2 < self.smoothing.window {
+                return Some(Smoothed {
+                    value: quantile(self.history.make_contiguous(), 0.5)?,
+                    outlier,
+                });
+            }
+            self.history.extend(self.outliers.drain(..));
+        } else {
+            self.outliers.clear();
+            self.history.push_back(value);
+        }
+        while self.history.len() > self.smoothing.window.max(1) {
+            self.history.pop_front();
+        }
+
+        Some(Smoothed {
+            value: quantile(self.history.make_contiguous(), 0.5)?,
+            outlier,
+        })
+    }
+
+    fn is_outlier(&self, value: f32) -> bool {
+        let Some(max_deviations) = self.smoothing.max_deviations else {
+            return false;
+        };
+        if self.history.len() < 3 {
+            return false;
+        }
+
+        let values = self.history.iter().copied().collect::<Vec<_>>();
+        let Some(median) = quantile(&values, 0.5) else {
+            return false;
+        };
+        let deviations = values
+            .iter()
+            .map(|v| (v - median).abs())
+            .collect::<Vec<_>>();
+        let mad = quantile(&deviations, 0.5).unwrap_or(0.0);
+
+        (value - median).abs() > max_deviations * mad.max(f32::EPSILON * median.abs().max(1.0))
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[path = "./test_smooth.rs"]
+mod test_smooth;
@@ -0,0 +1,80 @@
+use std::fmt::Write;
+
+use crate::{Field, Occupancy, Uplink};
+
+/// Renders an uplink in a stable text form for snapshot tests: one `name: value` line per decoded
+/// field in `Field::ALL` order, with a fixed number of decimals for each field, followed by values
+/// which are not a single `Field` and the undecoded identifiers. Absent fields are left out, so
+/// snapshots only change when a decoded value does.
+pub fn render(uplink: &Uplink) -> String {
+    let mut output = String::new();
+
+    for field in Field::ALL {
+        let value = match field {
+            Field::Temperature => uplink.temperature_f64().map(|t| format!("{:.1}", t)),
+            Field::Co2 => uplink.co2_ppm().map(|c| c.to_string()),
+            Field::Battery => uplink.battery_voltage_f64().map(|v| format!("{:.3}", v)),
+            Field::Occupancy => uplink.occupancy().map(|o| {
+                match o {
+                    Occupancy::NoBody => "no_body",
+                    Occupancy::PendingOrPir => "pending_or_pir",
+                    Occupancy::OccupiedOrHeat => "occupied_or_heat",
+                }
+                .to_string()
+            }),
+            Field::ExternalDigital => uplink.external_digital().map(|d| d.to_string()),
+            Field::Humidity => uplink.humidity_percent().map(|h| h.to_string()),
+            Field::Light => uplink.light_lux().map(|l| l.to_string()),
+            Field::Motion => uplink.motion_count().map(|m| m.to_string()),
+            Field::Pulse1 => uplink.pulse1().map(|p| p.to_string()),
+            Field::Pulse1Absolute => uplink.pulse1_absolute().map(|p| p.to_string()),
+            Field::ExternalTemperature1 => {
+                uplink.external_temperature_1().map(|t| format!("{:.1}", t))
+            }
+            Field::ExternalTemperature2 => {
+                uplink.external_temperature_2().map(|t| format!("{:.1}", t))
+            }
+            Field::Distance => uplink.distance_mm().map(|d| d.to_string()),
+            Field::AccelerationMotion => uplink.acceleration_motion().map(|m| m.to_string()),
+            Field::Waterleak => uplink.waterleak().map(|w| w.to_string()),
+        };
+        if let Some(value) = value {
+            let _ = writeln!(output, "{}: {}", field.name(), value);
+        }
+    }
+
+    if let Some([x, y, z]) = uplink.acceleration_raw() {
+        let _ = writeln!(output, "acceleration_raw: {} {} {}", x, y, z);
+    }
+    if let Some(position) = uplink.gps() {
+        let _ = writeln!(
+            output,
+            "gps: {:.4} {:.4}",
+            position.latitude, position.longitude
+        );
+    }
+
+    if !uplink.undecoded_identifiers().is_empty() {
+        output.push_str("undecoded:");
+        for identifier in uplink.undecoded_identifiers() {
+            let _ = write!(output, " 0x{:02x}", identifier);
+        }
+        output.push('\n');
+    }
+
+    output
+}
+
+/// Renders readings in the order given, each headed by its timestamp and separated by a blank line.
+pub fn render_readings(readings: &[(u64, Uplink)]) -> String {
+    readings
+        .iter()
+        .map(|(timestamp_s, uplink)| format!("timestamp_s: {}\n{}", timestamp_s, render(uplink)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[path = "./test_snapshot.rs"]
+mod test_snapshot;
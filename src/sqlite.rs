@@ -0,0 +1,212 @@
+use std::io::{Error, Result};
+use std::path::Path;
+
+use rusqlite::{params, Connection};
+
+use crate::{Occupancy, Uplink};
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS uplink (
+    dev_eui          INTEGER NOT NULL,
+    timestamp_s      INTEGER NOT NULL,
+    temperature      REAL,
+    co2_ppm          INTEGER,
+    battery_mv       INTEGER,
+    occupancy        INTEGER,
+    external_digital INTEGER
+);
+CREATE INDEX IF NOT EXISTS uplink_dev_eui_timestamp ON uplink (dev_eui, timestamp_s);
+";
+
+/// Columns for fields decoded since the table was first created, added to existing databases when
+/// they are opened.
+const ADDED_COLUMNS: &[(&str, &str)] = &[
+    ("humidity_percent", "INTEGER"),
+    ("light_lux", "INTEGER"),
+    ("motion_count", "INTEGER"),
+    ("acceleration_x", "INTEGER"),
+    ("acceleration_y", "INTEGER"),
+    ("acceleration_z", "INTEGER"),
+    ("gps_latitude_e4", "INTEGER"),
+    ("gps_longitude_e4", "INTEGER"),
+    ("pulse1", "INTEGER"),
+    ("pulse1_absolute", "INTEGER"),
+    ("external_temperature_1", "REAL"),
+    ("external_temperature_2", "REAL"),
+    ("distance_mm", "INTEGER"),
+    ("acceleration_motion", "INTEGER"),
+    ("waterleak", "INTEGER"),
+];
+
+/// Decoded uplinks buffered in an SQLite database. DevEUIs and timestamps are stored as their
+/// `i64` bit patterns.
+pub struct Store {
+    connection: Connection,
+}
+
+impl Store {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::new(Connection::open(path).map_err(Error::other)?)
+    }
+
+    pub fn open_in_memory() -> Result<Self> {
+        Self::new(Connection::open_in_memory().map_err(Error::other)?)
+    }
+
+    fn new(connection: Connection) -> Result<Self> {
+        connection.execute_batch(SCHEMA).map_err(Error::other)?;
+
+        let columns = connection
+            .prepare("SELECT name FROM pragma_table_info('uplink')")
+            .and_then(|mut statement| {
+                statement
+                    .query_map([], |row| row.get::<_, String>(0))?
+                    .collect::<rusqlite::Result<Vec<_>>>()
+            })
+            .map_err(Error::other)?;
+        for (name, column_type) in ADDED_COLUMNS {
+            if !columns.iter().any(|column| column == name) {
+                connection
+                    .execute_batch(&format!(
+                        "ALTER TABLE uplink ADD COLUMN {} {}",
+                        name, column_type
+                    ))
+                    .map_err(Error::other)?;
+            }
+        }
+
+        Ok(Self { connection })
+    }
+
+    pub fn insert(&self, dev_eui: u64, timestamp_s: u64, uplink: &Uplink) -> Result<()> {
+        self.connection
+            .prepare_cached(
+                "INSERT INTO uplink \
+                (dev_eui, timestamp_s, temperature, co2_ppm, battery_mv, occupancy, \
+                external_digital, humidity_percent, light_lux, motion_count, \
+                acceleration_x, acceleration_y, acceleration_z, \
+                gps_latitude_e4, gps_longitude_e4, pulse1, pulse1_absolute, \
+                external_temperature_1, external_temperature_2, distance_mm, \
+                acceleration_motion, waterleak) \
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, \
+                ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22)",
+            )
+            .and_then(|mut statement| {
+                statement.execute(params![
+                    dev_eui as i64,
+                    timestamp_s as i64,
+                    uplink.temperature,
+                    uplink.co2,
+                    uplink.battery_mv,
+                    uplink.occupancy.map(occupancy_to_int),
+                    uplink.external_digital,
+                    uplink.humidity,
+                    uplink.light,
+                    uplink.motion,
+                    uplink.acceleration.map(|[x, _, _]| x),
+                    uplink.acceleration.map(|[_, y, _]| y),
+                    uplink.acceleration.map(|[_, _, z]| z),
+                    uplink.gps.map(|[latitude, _]| latitude),
+                    uplink.gps.map(|[_, longitude]| longitude),
+                    uplink.pulse1,
+                    uplink.pulse1_absolute,
+                    uplink.external_temperature_1,
+                    uplink.external_temperature_2,
+                    uplink.distance,
+                    uplink.acceleration_motion,
+                    uplink.waterleak,
+                ])
+            })
+            .map_err(Error::other)?;
+        Ok(())
+    }
+
+    /// Uplinks of one device with `from_s <= timestamp_s < to_s`, oldest first.
+    pub fn range(&self, dev_eui: u64, from_s: u64, to_s: u64) -> Result<Vec<(u64, Uplink)>> {
+        let mut statement = self
+            .connection
+            .prepare_cached(
+                "SELECT timestamp_s, temperature, co2_ppm, battery_mv, occupancy, \
+                external_digital, humidity_percent, light_lux, motion_count, \
+                acceleration_x, acceleration_y, acceleration_z, gps_latitude_e4, gps_longitude_e4, \
+                pulse1, pulse1_absolute, external_temperature_1, external_temperature_2, \
+                distance_mm, acceleration_motion, waterleak \
+                FROM uplink WHERE dev_eui = ?1 AND timestamp_s >= ?2 AND timestamp_s < ?3 \
+                ORDER BY timestamp_s",
+            )
+            .map_err(Error::other)?;
+
+        let rows = statement
+            .query_map(params![dev_eui as i64, from_s as i64, to_s as i64], |row| {
+                let timestamp_s: i64 = row.get(0)?;
+                let occupancy: Option<u8> = row.get(4)?;
+                Ok((
+                    timestamp_s as u64,
+                    Uplink {
+                        temperature: row.get(1)?,
+                        co2: row.get(2)?,
+                        battery_mv: row.get(3)?,
+                        occupancy: occupancy.and_then(int_to_occupancy),
+                        external_digital: row.get(5)?,
+                        humidity: row.get(6)?,
+                        light: row.get(7)?,
+                        motion: row.get(8)?,
+                        acceleration: acceleration(row.get(9)?, row.get(10)?, row.get(11)?),
+                        gps: gps(row.get(12)?, row.get(13)?),
+                        pulse1: row.get(14)?,
+                        pulse1_absolute: row.get(15)?,
+                        external_temperature_1: row.get(16)?,
+                        external_temperature_2: row.get(17)?,
+                        distance: row.get(18)?,
+                        acceleration_motion: row.get(19)?,
+                        waterleak: row.get(20)?,
+                        undecoded: Vec::new(),
+                    },
+                ))
+            })
+            .map_err(Error::other)?;
+
+        rows.collect::<rusqlite::Result<Vec<_>>>()
+            .map_err(Error::other)
+    }
+
+    /// Deletes uplinks older than `before_s`, returning how many were deleted.
+    pub fn prune(&self, before_s: u64) -> Result<usize> {
+        self.connection
+            .execute(
+                "DELETE FROM uplink WHERE timestamp_s < ?1",
+                params![before_s as i64],
+            )
+            .map_err(Error::other)
+    }
+}
+
+fn acceleration(x: Option<i8>, y: Option<i8>, z: Option<i8>) -> Option<[i8; 3]> {
+    Some([x?, y?, z?])
+}
+
+fn gps(latitude: Option<i32>, longitude: Option<i32>) -> Option<[i32; 2]> {
+    Some([latitude?, longitude?])
+}
+
+fn occupancy_to_int(occupancy: Occupancy) -> u8 {
+    match occupancy {
+        Occupancy::NoBody => 0,
+        Occupancy::PendingOrPir => 1,
+        Occupancy::OccupiedOrHeat => 2,
+    }
+}
+
+fn int_to_occupancy(value: u8) -> Option<Occupancy> {
+    match value {
+        0 => Some(Occupancy::NoBody),
+        1 => Some(Occupancy::PendingOrPir),
+        2 => Some(Occupancy::OccupiedOrHeat),
+        _ => None,
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[path = "./test_sqlite.rs"]
+mod test_sqlite;
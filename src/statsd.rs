@@ -0,0 +1,133 @@
+use std::io::Result;
+use std::net::{ToSocketAddrs, UdpSocket};
+
+use crate::metrics::{Snapshot, LATENCY_BUCKETS_NS};
+use crate::{Field, Uplink};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Flavour {
+    /// Plain StatsD, which has no tags: the DevEUI becomes part of the metric name.
+    StatsD,
+    /// DogStatsD, which tags sensor gauges with `dev_eui`.
+    DogStatsD,
+}
+
+/// Sends decode counters and sensor gauges as StatsD packets over UDP.
+pub struct Emitter {
+    socket: UdpSocket,
+    prefix: String,
+    flavour: Flavour,
+    previous: Snapshot,
+}
+
+impl Emitter {
+    pub fn new<A: ToSocketAddrs>(target: A, prefix: &str, flavour: Flavour) -> Result<Self> {
+        let socket = UdpSocket::bind("0.0.0.0:0")?;
+        socket.connect(target)?;
+        Ok(Self {
+            socket,
+            prefix: String::from(prefix),
+            flavour,
+            previous: Snapshot::default(),
+        })
+    }
+
+    /// Sends what has changed since the previous snapshot as counters.
+    pub fn send_metrics(&mut self, snapshot: &Snapshot) -> Result<()> {
+        let lines = metric_lines(&self.prefix, &self.previous, snapshot);
+        self.previous = snapshot.clone();
+        self.send(&lines)
+    }
+
+    pub fn send_uplink(&self, dev_eui: u64, uplink: &Uplink, fields: &[Field]) -> Result<()> {
+        self.send(&uplink_lines(
+            &self.prefix,
+            self.flavour,
+            dev_eui,
+            uplink,
+            fields,
+        ))
+    }
+
+    fn send(&self, lines: &[String]) -> Result<()> {
+        if !lines.is_empty() {
+            self.socket.send(lines.join("\n").as_bytes())?;
+        }
+        Ok(())
+    }
+}
+
+fn metric_lines(prefix: &str, previous: &Snapshot, current: &Snapshot) -> Vec<String> {
+    let mut output = Vec::new();
+    let mut counter = |name: String, previous: u64, current: u64| {
+        if current > previous {
+            output.push(format!("{}.{}:{}|c", prefix, name, current - previous));
+        }
+    };
+
+    counter(
+        String::from("decode.success"),
+        previous.successes,
+        current.successes,
+    );
+    counter(
+        String::from("decode.failure"),
+        previous.failures,
+        current.failures,
+    );
+
+    for (identifier, count) in &current.identifiers {
+        let before = previous
+            .identifiers
+            .iter()
+            .find(|(i, _)| i == identifier)
+            .map_or(0, |(_, c)| *c);
+        counter(format!("identifier.0x{:02x}", identifier), before, *count);
+    }
+
+    for (bucket, count) in current.latency.iter().enumerate() {
+        let name = match LATENCY_BUCKETS_NS.get(bucket) {
+            Some(bound_ns) => format!("latency.le_{}ns", bound_ns),
+            None => String::from("latency.le_inf"),
+        };
+        counter(
+            name,
+            previous.latency.get(bucket).copied().unwrap_or(0),
+            *count,
+        );
+    }
+
+    output
+}
+
+fn uplink_lines(
+    prefix: &str,
+    flavour: Flavour,
+    dev_eui: u64,
+    uplink: &Uplink,
+    fields: &[Field],
+) -> Vec<String> {
+    fields
+        .iter()
+        .filter_map(|field| {
+            let value = uplink.value(*field)?;
+            Some(match flavour {
+                Flavour::StatsD => {
+                    format!("{}.{:016x}.{}:{}|g", prefix, dev_eui, field.name(), value)
+                }
+                Flavour::DogStatsD => format!(
+                    "{}.{}:{}|g|#dev_eui:{:016x}",
+                    prefix,
+                    field.name(),
+                    value,
+                    dev_eui
+                ),
+            })
+        })
+        .collect()
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[path = "./test_statsd.rs"]
+mod test_statsd;
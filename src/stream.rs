@@ -0,0 +1,106 @@
+use std::io::{BufRead, BufReader, Error, ErrorKind, Read, Result};
+
+use crate::record::read_unless_eof;
+use crate::Uplink;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Framing {
+    /// Each payload is preceded by its length as a big-endian `u16`.
+    LengthPrefixed,
+    /// One hex-encoded payload per line. Blank lines are skipped.
+    HexLines,
+}
+
+/// Decodes uplinks from a stream of framed payloads.
+///
+/// A payload which does not decode yields an error and the stream carries on with the next one. An
+/// error reading the stream, or a stream which ends mid-frame, yields an error and ends it.
+pub struct UplinkStream<R: Read> {
+    reader: BufReader<R>,
+    framing: Framing,
+    line: String,
+    finished: bool,
+}
+
+impl<R: Read> UplinkStream<R> {
+    pub fn new(reader: R, framing: Framing) -> Self {
+        Self {
+            reader: BufReader::new(reader),
+            framing,
+            line: String::new(),
+            finished: false,
+        }
+    }
+
+    fn next_payload(&mut self) -> Result<Option<Vec<u8>>> {
+        match self.framing {
+            Framing::LengthPrefixed => {
+                let mut length = [0; 2];
+                if !read_unless_eof(&mut self.reader, &mut length)? {
+                    return Ok(None);
+                }
+                let mut payload = vec![0; u16::from_be_bytes(length) as usize];
+                self.reader.read_exact(&mut payload)?;
+                Ok(Some(payload))
+            }
+            Framing::HexLines => loop {
+                self.line.clear();
+                if self.reader.read_line(&mut self.line)? == 0 {
+                    return Ok(None);
+                }
+                let line = self.line.trim();
+                if !line.is_empty() {
+                    return decode_hex(line).map(Some);
+                }
+            },
+        }
+    }
+}
+
+impl<R: Read> Iterator for UplinkStream<R> {
+    type Item = Result<Uplink>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        match self.next_payload() {
+            Ok(Some(payload)) => Some(Uplink::deserialize(&payload)),
+            Ok(None) => {
+                self.finished = true;
+                None
+            }
+            Err(e) if e.kind() == ErrorKind::InvalidData => Some(Err(e)),
+            Err(e) => {
+                self.finished = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+pub(crate) fn decode_hex(line: &str) -> Result<Vec<u8>> {
+    let invalid = || {
+        Error::new(
+            ErrorKind::InvalidData,
+            format!("{}-character line is not a hex payload", line.len()),
+        )
+    };
+
+    if !line.len().is_multiple_of(2) {
+        return Err(invalid());
+    }
+    (0..line.len())
+        .step_by(2)
+        .map(|i| {
+            line.get(i..i + 2)
+                .and_then(|byte| u8::from_str_radix(byte, 16).ok())
+                .ok_or_else(invalid)
+        })
+        .collect()
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[path = "./test_stream.rs"]
+mod test_stream;
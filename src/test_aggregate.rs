@@ -0,0 +1,80 @@
+use super::*;
+
+const WINDOW_S: u64 = 15 * 60;
+
+#[test]
+fn aggregate_per_window_and_field() {
+    let readings = [
+        (0, Uplink { temperature: Some(20.0), co2: Some(400), ..Uplink::default() }),
+        (300, Uplink { temperature: Some(22.0), ..Uplink::default() }),
+        (600, Uplink { temperature: Some(21.0), co2: Some(500), ..Uplink::default() }),
+        (WINDOW_S, Uplink { co2: Some(450), ..Uplink::default() }),
+    ];
+
+    assert_eq!(
+        vec![
+            Aggregate {
+                start_s: 0,
+                field: Field::Temperature,
+                stats: Stats { count: 3, min: 20.0, max: 22.0, mean: 21.0, last: 21.0 },
+            },
+            Aggregate {
+                start_s: 0,
+                field: Field::Co2,
+                stats: Stats { count: 2, min: 400.0, max: 500.0, mean: 450.0, last: 500.0 },
+            },
+            Aggregate {
+                start_s: WINDOW_S,
+                field: Field::Co2,
+                stats: Stats { count: 1, min: 450.0, max: 450.0, mean: 450.0, last: 450.0 },
+            },
+        ],
+        aggregate(&readings, WINDOW_S)
+    );
+}
+
+#[test]
+fn aggregate_zero_window() {
+    let readings = [(0, Uplink { co2: Some(400), ..Uplink::default() })];
+
+    assert!(aggregate(&readings, 0).is_empty());
+}
+
+#[test]
+fn aggregate_last_is_latest_reading() {
+    let readings = [
+        (600, Uplink { co2: Some(500), ..Uplink::default() }),
+        (300, Uplink { co2: Some(400), ..Uplink::default() }),
+    ];
+
+    assert_eq!(500.0, aggregate(&readings, WINDOW_S)[0].stats.last);
+}
+
+#[test]
+fn aggregate_empty() {
+    assert!(aggregate(&[], WINDOW_S).is_empty());
+}
+
+#[test]
+fn test_quantile() {
+    let values = [5.0, 1.0, 4.0, 2.0, 3.0];
+
+    assert_eq!(Some(1.0), quantile(&values, 0.0));
+    assert_eq!(Some(3.0), quantile(&values, 0.5));
+    assert_eq!(Some(4.0), quantile(&values, 0.8));
+    assert_eq!(Some(5.0), quantile(&values, 1.0));
+    assert_eq!(None, quantile(&[], 0.5));
+}
+
+#[test]
+fn aggregate_large_absolute_pulse_count() {
+    let readings = [
+        (0, Uplink { pulse1_absolute: Some(16_777_217), ..Uplink::default() }),
+        (60, Uplink { pulse1_absolute: Some(16_777_219), ..Uplink::default() }),
+    ];
+
+    assert_eq!(
+        vec![Aggregate { start_s: 0, field: Field::Pulse1Absolute, stats: Stats { count: 2, min: 16_777_217.0, max: 16_777_219.0, mean: 16_777_218.0, last: 16_777_219.0 } }],
+        aggregate(&readings, WINDOW_S)
+    );
+}
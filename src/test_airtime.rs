@@ -0,0 +1,52 @@
+use super::*;
+use crate::region::Region;
+
+#[test]
+fn airtime() {
+    assert_eq!(Some(56_576), airtime_us(&Modulation::lorawan(7, 125_000), 20));
+    assert_eq!(Some(1_318_912), airtime_us(&Modulation::lorawan(12, 125_000), 20));
+    assert_eq!(Some(28_288), airtime_us(&Modulation::lorawan(7, 250_000), 20));
+    assert_eq!(airtime_us(&Modulation::lorawan(9, 125_000), 24), uplink_airtime_us(&Modulation::lorawan(9, 125_000), 11));
+}
+
+#[test]
+fn invalid_spreading_factor() {
+    assert_eq!(None, airtime_us(&Modulation::lorawan(4, 125_000), 20));
+    assert_eq!(None, airtime_us(&Modulation::lorawan(13, 125_000), 20));
+    assert_eq!(None, uplink_airtime_us(&Modulation::lorawan(64, 125_000), 20));
+    assert!(airtime_us(&Modulation::lorawan(5, 125_000), 20).is_some());
+    assert!(!Modulation::lorawan(255, 125_000).low_data_rate_optimize());
+}
+
+#[test]
+fn low_data_rate_optimize() {
+    assert!(!Modulation::lorawan(10, 125_000).low_data_rate_optimize());
+    assert!(Modulation::lorawan(11, 125_000).low_data_rate_optimize());
+    assert!(!Modulation::lorawan(11, 250_000).low_data_rate_optimize());
+}
+
+#[test]
+fn duty_cycle() {
+    let mut duty_cycle = DutyCycle::new(Region::Eu868.sub_bands(), 3600);
+
+    assert_eq!(Some(36_000_000), duty_cycle.remaining_us(0, 868_100_000));
+    duty_cycle.record(0, 868_100_000, 30_000_000).unwrap();
+    duty_cycle.record(100, 868_300_000, 5_000_000).unwrap();
+
+    assert_eq!(Some(35_000_000), duty_cycle.used_us(200, 868_500_000));
+    assert!(duty_cycle.allows(200, 868_500_000, 1_000_000));
+    assert!(!duty_cycle.allows(200, 868_500_000, 1_000_001));
+    assert!(duty_cycle.allows(200, 869_525_000, 100_000_000));
+
+    assert_eq!(Some(5_000_000), duty_cycle.used_us(3600, 868_100_000));
+    assert_eq!(Some(0), duty_cycle.used_us(3700, 868_100_000));
+}
+
+#[test]
+fn outside_sub_bands() {
+    let mut duty_cycle = DutyCycle::new(Region::Eu868.sub_bands(), 3600);
+
+    assert!(duty_cycle.record(0, 915_000_000, 1000).is_err());
+    assert_eq!(None, duty_cycle.used_us(0, 868_650_000));
+    assert!(!duty_cycle.allows(0, 868_650_000, 1));
+}
@@ -0,0 +1,57 @@
+use super::*;
+
+const DISABLED: Thresholds = Thresholds { window: 10, max_z_score: None, max_rate_per_hour: None, max_repeats: None };
+
+fn temperature(t: f32) -> Uplink {
+    Uplink { temperature: Some(t), ..Uplink::default() }
+}
+
+#[test]
+fn detects_z_score() {
+    let mut detector = Detector::new();
+    detector.set_thresholds(Field::Temperature, Thresholds { max_z_score: Some(3.0), ..DISABLED });
+
+    for (i, t) in [20.0, 21.0, 20.0, 21.0, 20.0].iter().enumerate() {
+        assert!(detector.push(i as u64 * 600, &temperature(*t)).is_empty());
+    }
+
+    let anomalies = detector.push(3000, &temperature(30.0));
+    assert_eq!(1, anomalies.len());
+    assert_eq!(Field::Temperature, anomalies[0].field);
+    assert!(matches!(anomalies[0].kind, Kind::ZScore(z) if z > 3.0));
+}
+
+#[test]
+fn detects_rate_of_change() {
+    let mut detector = Detector::new();
+    detector.set_thresholds(Field::Temperature, Thresholds { max_rate_per_hour: Some(5.0), ..DISABLED });
+
+    assert!(detector.push(0, &temperature(20.0)).is_empty());
+    assert!(detector.push(1800, &temperature(22.0)).is_empty());
+    assert_eq!(
+        vec![Anomaly { timestamp_s: 2400, field: Field::Temperature, value: 25.0, kind: Kind::RateOfChange(18.0) }],
+        detector.push(2400, &temperature(25.0))
+    );
+}
+
+#[test]
+fn detects_stuck_once_per_run() {
+    let mut detector = Detector::new();
+    detector.set_thresholds(Field::Co2, Thresholds { max_repeats: Some(2), ..DISABLED });
+    let co2 = |ppm| Uplink { co2: Some(ppm), ..Uplink::default() };
+
+    assert!(detector.push(0, &co2(500)).is_empty());
+    assert!(detector.push(1, &co2(500)).is_empty());
+    assert_eq!(Kind::Stuck(3), detector.push(2, &co2(500))[0].kind);
+    assert!(detector.push(3, &co2(500)).is_empty());
+    assert!(detector.push(4, &co2(510)).is_empty());
+}
+
+#[test]
+fn ignores_fields_without_thresholds() {
+    let mut detector = Detector::new();
+    detector.set_thresholds(Field::Co2, Thresholds { max_rate_per_hour: Some(1.0), ..DISABLED });
+
+    assert!(detector.push(0, &temperature(20.0)).is_empty());
+    assert!(detector.push(60, &temperature(40.0)).is_empty());
+}
@@ -0,0 +1,44 @@
+use super::*;
+
+fn records() -> Vec<Record> {
+    (0..1000)
+        .map(|i| Record { timestamp_s: 1_700_000_000 + i * 600, dev_eui: 0xa81758fffe000000 + i % 4, payload: vec![0x01, 0x00, 0xdc, 0x07, 0x0e, 0x41] })
+        .collect()
+}
+
+#[test]
+fn round_trip() {
+    let mut writer = Writer::new(Vec::new()).unwrap();
+    for record in records() {
+        writer.write(&record).unwrap();
+    }
+    let archive = writer.finish().unwrap();
+
+    assert!(archive.len() < records().iter().map(Record::encoded_len).sum::<usize>() / 4);
+    assert_eq!(records(), Reader::new(archive.as_slice()).unwrap().collect::<Result<Vec<_>>>().unwrap());
+}
+
+#[test]
+fn empty() {
+    let archive = Writer::new(Vec::new()).unwrap().finish().unwrap();
+
+    assert_eq!(0, Reader::new(archive.as_slice()).unwrap().count());
+}
+
+#[test]
+fn not_an_archive() {
+    assert!(Reader::new(&b"ELSYSAR0"[..]).is_err());
+    assert!(Reader::new(&b"ELS"[..]).is_err());
+}
+
+#[test]
+fn truncated() {
+    let mut writer = Writer::new(Vec::new()).unwrap();
+    for record in records() {
+        writer.write(&record).unwrap();
+    }
+    let archive = writer.finish().unwrap();
+
+    let result = Reader::new(&archive[..archive.len() / 2]).unwrap().collect::<Result<Vec<_>>>();
+    assert!(result.is_err());
+}
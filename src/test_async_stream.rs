@@ -0,0 +1,62 @@
+use super::*;
+
+async fn collect<S: Stream + Unpin>(mut stream: S) -> Vec<S::Item> {
+    let mut output = Vec::new();
+    while let Some(item) = std::future::poll_fn(|cx| Pin::new(&mut stream).poll_next(cx)).await {
+        output.push(item);
+    }
+    output
+}
+
+/// A stream over the items of a vector.
+struct Items<T>(std::vec::IntoIter<T>);
+
+impl<T: Unpin> Stream for Items<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, _: &mut Context) -> Poll<Option<T>> {
+        Poll::Ready(self.0.next())
+    }
+}
+
+#[tokio::test]
+async fn length_prefixed() {
+    let (mut writer, reader) = tokio::io::duplex(1);
+    tokio::spawn(async move {
+        tokio::io::AsyncWriteExt::write_all(&mut writer, &[0x00, 0x03, 0x01, 0x00, 0xdc, 0x00, 0x03, 0x06, 0x02, 0x58]).await.unwrap();
+    });
+
+    let uplinks = collect(AsyncUplinkStream::new(reader, Framing::LengthPrefixed)).await.into_iter().collect::<Result<Vec<_>>>().unwrap();
+    assert_eq!(vec![Some(22.0), None], uplinks.iter().map(Uplink::temperature).collect::<Vec<_>>());
+    assert_eq!(vec![None, Some(600)], uplinks.iter().map(Uplink::co2_ppm).collect::<Vec<_>>());
+}
+
+#[tokio::test]
+async fn length_prefixed_truncated() {
+    let results = collect(AsyncUplinkStream::new(&[0x00, 0x03, 0x01, 0x00, 0xdc, 0x00, 0x03, 0x06][..], Framing::LengthPrefixed)).await;
+
+    assert_eq!(2, results.len());
+    assert!(results[0].is_ok());
+    assert_eq!(ErrorKind::UnexpectedEof, results[1].as_ref().unwrap_err().kind());
+}
+
+#[tokio::test]
+async fn hex_lines() {
+    let results = collect(AsyncUplinkStream::new(&b"0100dc\n\n060258\r\n20\nzz\n1102"[..], Framing::HexLines)).await;
+
+    assert_eq!(5, results.len());
+    assert_eq!(Some(22.0), results[0].as_ref().unwrap().temperature());
+    assert_eq!(Some(600), results[1].as_ref().unwrap().co2_ppm());
+    assert!(results[2].is_err());
+    assert!(results[3].is_err());
+    assert_eq!(Some(crate::Occupancy::OccupiedOrHeat), results[4].as_ref().unwrap().occupancy());
+}
+
+#[tokio::test]
+async fn decode() {
+    let payloads = vec![vec![0x01, 0x00, 0xdc], vec![0x20]];
+
+    let results = collect(Decode::new(Items(payloads.into_iter()))).await;
+    assert_eq!(Some(22.0), results[0].as_ref().unwrap().temperature());
+    assert!(results[1].is_err());
+}
@@ -0,0 +1,53 @@
+use super::*;
+
+const DEVICE_A: u64 = 0xa81758fffe000001;
+const DEVICE_B: u64 = 0xa81758fffe000002;
+
+fn analog(instance: u32) -> ObjectId {
+    ObjectId { object_type: ObjectType::AnalogInput, instance }
+}
+
+#[test]
+fn add_device() {
+    let mut objects = Objects::new();
+
+    assert!(objects.add_device(DEVICE_A, 1).is_ok());
+    assert!(objects.add_device(DEVICE_B, 1).is_err());
+    assert!(objects.add_device(DEVICE_B, MAX_INSTANCE / INSTANCES_PER_DEVICE).is_ok());
+    assert!(objects.add_device(DEVICE_B, MAX_INSTANCE / INSTANCES_PER_DEVICE + 1).is_err());
+}
+
+#[test]
+fn update() {
+    let mut objects = Objects::new();
+    objects.add_device(DEVICE_A, 2).unwrap();
+
+    let uplink = Uplink { temperature: Some(21.5), co2: Some(640), occupancy: Some(Occupancy::OccupiedOrHeat), external_digital: Some(true), ..Uplink::default() };
+    assert_eq!(vec![
+        analog(32),
+        analog(33),
+        ObjectId { object_type: ObjectType::MultiStateInput, instance: 35 },
+        ObjectId { object_type: ObjectType::BinaryInput, instance: 36 },
+    ], objects.update(DEVICE_A, &uplink));
+
+    let temperature = objects.get(analog(32)).unwrap();
+    assert_eq!("a81758fffe000001.temperature", temperature.name);
+    assert_eq!(Some(62), temperature.units);
+    assert_eq!("a81758fffe000001.co2_ppm", objects.get(analog(33)).unwrap().name);
+    assert_eq!(PresentValue::Real(21.5), temperature.present_value);
+    let occupancy = objects.get(ObjectId { object_type: ObjectType::MultiStateInput, instance: 35 }).unwrap();
+    assert_eq!(PresentValue::State(3), occupancy.present_value);
+    assert_eq!(3, occupancy.state_text.len());
+
+    assert_eq!(vec![analog(33)], objects.update(DEVICE_A, &Uplink { temperature: Some(21.5), co2: Some(700), ..Uplink::default() }));
+    assert_eq!(PresentValue::Real(700.0), objects.get(analog(33)).unwrap().present_value);
+    assert_eq!(4, objects.iter().count());
+}
+
+#[test]
+fn unknown_device() {
+    let mut objects = Objects::new();
+
+    assert!(objects.update(DEVICE_A, &Uplink { temperature: Some(21.5), ..Uplink::default() }).is_empty());
+    assert_eq!(0, objects.iter().count());
+}
@@ -0,0 +1,65 @@
+use super::*;
+
+struct TempPath(std::path::PathBuf);
+
+impl TempPath {
+    fn new(name: &str) -> Self {
+        Self(std::env::temp_dir().join(format!("elsys_test_buffer_{}_{}.redb", name, std::process::id())))
+    }
+}
+
+impl Drop for TempPath {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+fn record(timestamp_s: u64) -> Record {
+    Record { timestamp_s, dev_eui: 0xa81758fffe000001, payload: vec![0x06, 0x02, 0x58] }
+}
+
+#[test]
+fn push_and_acknowledge() {
+    let path = TempPath::new("acknowledge");
+    let buffer = Buffer::open(&path.0, 10).unwrap();
+    assert!(buffer.is_empty().unwrap());
+    assert!(buffer.pending(10).unwrap().is_empty());
+
+    for timestamp_s in 0..4 {
+        assert_eq!(timestamp_s, buffer.push(&record(timestamp_s)).unwrap());
+    }
+
+    assert_eq!(vec![(0, record(0)), (1, record(1))], buffer.pending(2).unwrap());
+    assert_eq!(2, buffer.acknowledge(1).unwrap());
+    assert_eq!(vec![(2, record(2)), (3, record(3))], buffer.pending(10).unwrap());
+    assert_eq!(2, buffer.acknowledge(3).unwrap());
+    assert_eq!(4, buffer.push(&record(4)).unwrap());
+}
+
+#[test]
+fn evicts_oldest() {
+    let path = TempPath::new("evict");
+    let buffer = Buffer::open(&path.0, 3).unwrap();
+
+    for timestamp_s in 0..5 {
+        buffer.push(&record(timestamp_s)).unwrap();
+    }
+
+    assert_eq!(3, buffer.len().unwrap());
+    assert_eq!(vec![2, 3, 4], buffer.pending(10).unwrap().iter().map(|(s, _)| *s).collect::<Vec<_>>());
+}
+
+#[test]
+fn persists() {
+    let path = TempPath::new("persist");
+    {
+        let buffer = Buffer::open(&path.0, 10).unwrap();
+        buffer.push(&record(0)).unwrap();
+        buffer.push(&record(1)).unwrap();
+        buffer.acknowledge(0).unwrap();
+    }
+
+    let buffer = Buffer::open(&path.0, 10).unwrap();
+    assert_eq!(vec![(1, record(1))], buffer.pending(10).unwrap());
+    assert_eq!(2, buffer.push(&record(2)).unwrap());
+}
@@ -0,0 +1,53 @@
+use super::*;
+
+const A: [u8; 3] = [0x01, 0x00, 0xdc];
+const B: [u8; 3] = [0x06, 0x02, 0x58];
+const C: [u8; 2] = [0x11, 0x02];
+
+#[test]
+fn hits() {
+    let mut cache = Cache::new(2);
+
+    assert_eq!(Uplink::deserialize(&A).unwrap(), cache.deserialize(&A).unwrap());
+    assert_eq!(Uplink::deserialize(&A).unwrap(), cache.deserialize(&A).unwrap());
+    assert_eq!(1, cache.hits());
+    assert_eq!(1, cache.misses());
+    assert_eq!(1, cache.len());
+}
+
+#[test]
+fn evicts_least_recently_used() {
+    let mut cache = Cache::new(2);
+
+    cache.deserialize(&A).unwrap();
+    cache.deserialize(&B).unwrap();
+    cache.deserialize(&A).unwrap();
+    cache.deserialize(&C).unwrap();
+    assert_eq!(2, cache.len());
+
+    cache.deserialize(&A).unwrap();
+    assert_eq!(2, cache.hits());
+    cache.deserialize(&B).unwrap();
+    assert_eq!(2, cache.hits());
+    assert_eq!(4, cache.misses());
+}
+
+#[test]
+fn errors_not_cached() {
+    let mut cache = Cache::new(2);
+
+    assert!(cache.deserialize(&[0x20]).is_err());
+    assert!(cache.deserialize(&[0x20]).is_err());
+    assert!(cache.is_empty());
+    assert_eq!(2, cache.misses());
+}
+
+#[test]
+fn zero_capacity() {
+    let mut cache = Cache::new(0);
+
+    assert!(cache.deserialize(&A).is_ok());
+    assert!(cache.deserialize(&A).is_ok());
+    assert!(cache.is_empty());
+    assert_eq!(0, cache.hits());
+}
@@ -0,0 +1,53 @@
+use super::*;
+
+/// Monday 2024-03-04 00:00 UTC.
+const MONDAY_S: u64 = 1_709_510_400;
+
+#[test]
+fn days() {
+    assert_eq!(0, day_number(1970, 1, 1));
+    assert_eq!(MONDAY_S / DAY_S, day_number(2024, 3, 4));
+    assert_eq!(0, day_number(1969, 12, 31));
+
+    for days in [-719468, -1, 0, 59, 19_786, 2_932_896] {
+        let (year, month, day) = civil_from_days(days);
+        assert_eq!(days, days_from_civil(year, month, day));
+    }
+    assert_eq!((2024, 2, 29), civil_from_days(19_782));
+    assert_eq!("2024-03-04T12:34:56Z", format_rfc3339_s(MONDAY_S + 45_296));
+}
+
+#[test]
+fn office_hours() {
+    let calendar = Calendar::office(0);
+
+    assert!(!calendar.contains(MONDAY_S + 8 * HOUR_S - 1));
+    assert!(calendar.contains(MONDAY_S + 8 * HOUR_S));
+    assert!(calendar.contains(MONDAY_S + 18 * HOUR_S - 1));
+    assert!(!calendar.contains(MONDAY_S + 18 * HOUR_S));
+    assert!(calendar.contains(MONDAY_S + 4 * DAY_S + 12 * HOUR_S));
+    assert!(!calendar.contains(MONDAY_S + 5 * DAY_S + 12 * HOUR_S));
+
+    assert_eq!(50 * HOUR_S, calendar.open_s(MONDAY_S, MONDAY_S + 7 * DAY_S));
+    assert_eq!(HOUR_S + 1800, calendar.open_s(MONDAY_S + 17 * HOUR_S, MONDAY_S + DAY_S + 8 * HOUR_S + 1800));
+}
+
+#[test]
+fn offset_and_holidays() {
+    let mut calendar = Calendar::office(2 * HOUR_S as i64);
+    calendar.add_holiday(2024, 3, 5);
+
+    assert!(calendar.contains(MONDAY_S + 6 * HOUR_S));
+    assert!(!calendar.contains(MONDAY_S + 16 * HOUR_S));
+    assert!(!calendar.contains(MONDAY_S + DAY_S + 10 * HOUR_S));
+    assert_eq!(40 * HOUR_S, calendar.open_s(MONDAY_S, MONDAY_S + 7 * DAY_S));
+}
+
+#[test]
+fn within() {
+    let calendar = Calendar::office(0);
+    let reading = |timestamp_s| (timestamp_s, Uplink { co2: Some(600), ..Uplink::default() });
+    let readings = [reading(MONDAY_S + 7 * HOUR_S), reading(MONDAY_S + 9 * HOUR_S), reading(MONDAY_S + 5 * DAY_S + 9 * HOUR_S)];
+
+    assert_eq!(vec![MONDAY_S + 9 * HOUR_S], calendar.within(&readings).iter().map(|(t, _)| *t).collect::<Vec<_>>());
+}
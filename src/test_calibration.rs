@@ -0,0 +1,48 @@
+use super::*;
+
+const CALIBRATION: Calibration = Calibration { temperature_offset: -0.5, co2_offset_ppm: 20.0, co2_slope: 0.9, humidity_offset_percent: 3.0, external_temperature_1_offset: 0.25, external_temperature_2_offset: -1.0, motion_per_person: 2.5 };
+
+#[test]
+fn apply_calibration() {
+    let mut uplink = Uplink { temperature: Some(22.0), co2: Some(800), battery_mv: Some(3600), humidity: Some(45), external_temperature_1: Some(-5.0), external_temperature_2: Some(60.0), ..Uplink::default() };
+    uplink.apply_calibration(&CALIBRATION);
+
+    assert_eq!(Uplink { temperature: Some(21.5), co2: Some(740), battery_mv: Some(3600), humidity: Some(48), external_temperature_1: Some(-4.75), external_temperature_2: Some(59.0), ..Uplink::default() }, uplink);
+}
+
+#[test]
+fn apply_calibration_saturates() {
+    let mut uplink = Uplink { co2: Some(10), ..Uplink::default() };
+    uplink.apply_calibration(&Calibration { co2_offset_ppm: -50.0, ..Calibration::default() });
+
+    assert_eq!(Some(0), uplink.co2_ppm());
+
+    let mut uplink = Uplink { humidity: Some(99), ..Uplink::default() };
+    uplink.apply_calibration(&Calibration { humidity_offset_percent: 2.6, ..Calibration::default() });
+    assert_eq!(Some(100), uplink.humidity_percent());
+}
+
+#[test]
+fn footfall() {
+    let motion = |count| (0, Uplink { motion: Some(count), ..Uplink::default() });
+    let readings = [motion(10), (0, Uplink::default()), motion(255), motion(5)];
+
+    assert_eq!(Some(108.0), CALIBRATION.footfall(&readings));
+    assert_eq!(None, CALIBRATION.footfall(&readings[1..2]));
+    assert_eq!(None, Calibration { motion_per_person: 0.0, ..CALIBRATION }.footfall(&readings));
+
+    let mut calibrations = Calibrations::new();
+    calibrations.insert(0x70b3d5e75e000001, CALIBRATION);
+    assert_eq!(Some(108.0), calibrations.footfall(0x70b3d5e75e000001, &readings));
+    assert_eq!(Some(270.0), calibrations.footfall(0x70b3d5e75e000002, &readings));
+}
+
+#[test]
+fn deserialize_per_device() {
+    let mut calibrations = Calibrations::new();
+    calibrations.insert(0x70b3d5e75e000001, CALIBRATION);
+
+    assert_eq!(Some(21.5), calibrations.deserialize(0x70b3d5e75e000001, &[0x01, 0x00, 0xdc]).unwrap().temperature());
+    assert_eq!(Some(22.0), calibrations.deserialize(0x70b3d5e75e000002, &[0x01, 0x00, 0xdc]).unwrap().temperature());
+    assert!(calibrations.deserialize(0x70b3d5e75e000001, &[0x01, 0x00]).is_err());
+}
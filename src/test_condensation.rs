@@ -0,0 +1,24 @@
+use super::*;
+
+fn uplink(temperature: f32, humidity: u8, surface: Option<f32>) -> Uplink {
+    Uplink { temperature: Some(temperature), humidity: Some(humidity), external_temperature_1: surface, ..Uplink::default() }
+}
+
+#[test]
+fn magnus() {
+    assert!((dew_point(20.0, 50.0).unwrap() - 9.26).abs() < 0.05);
+    assert!((dew_point(5.0, 100.0).unwrap() - 5.0).abs() < 0.001);
+    assert!((dew_point(-10.0, 80.0).unwrap() + 12.8).abs() < 0.1);
+    assert_eq!(None, dew_point(20.0, 0.0));
+    assert_eq!(None, dew_point(20.0, 101.0));
+}
+
+#[test]
+fn derived_values() {
+    let window = uplink(20.0, 50, Some(8.0));
+    assert!((Derived::DewPoint.value(&window).unwrap() - 9.26).abs() < 0.05);
+    assert!((Derived::DewPointMargin.value(&window).unwrap() + 1.26).abs() < 0.05);
+
+    assert_eq!(None, Derived::DewPointMargin.value(&uplink(20.0, 50, None)));
+    assert_eq!(None, Derived::DewPoint.value(&Uplink { temperature: Some(20.0), ..Uplink::default() }));
+}
@@ -0,0 +1,75 @@
+use super::*;
+use crate::rules::Severity;
+
+#[test]
+fn full() {
+    let config = Config::from_toml(
+        r#"
+        [limits]
+        max_payload_bytes = 51
+
+        [[device]]
+        dev_eui = "A81758FFFE000001"
+        calibration = { temperature_offset = -0.5, co2_offset_ppm = 20.0 }
+
+        [[device]]
+        dev_eui = "a81758fffe000002"
+
+        [[rule]]
+        name = "co2 high"
+        field = "co2"
+        operator = "above"
+        threshold = 1000.0
+        severity = "warning"
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(Limits { max_payload_bytes: 51, ..Limits::default() }, config.limits);
+    assert_eq!(
+        Some(&Calibration { temperature_offset: -0.5, co2_offset_ppm: 20.0, co2_slope: 1.0, humidity_offset_percent: 0.0, external_temperature_1_offset: 0.0, external_temperature_2_offset: 0.0, motion_per_person: 1.0 }),
+        config.calibrations.get(0xa81758fffe000001)
+    );
+    assert_eq!(Some(&Calibration::default()), config.calibrations.get(0xa81758fffe000002));
+    assert_eq!(None, config.calibrations.get(0xa81758fffe000003));
+    assert_eq!(1, config.rules.len());
+    assert_eq!(Severity::Warning, config.engine().rules().next().unwrap().severity);
+}
+
+#[test]
+fn empty() {
+    let config = Config::from_toml("").unwrap();
+
+    assert_eq!(Limits::default(), config.limits);
+    assert!(config.rules.is_empty());
+}
+
+#[test]
+fn invalid() {
+    assert!(Config::from_toml("[limits]\nmax_records = -1").is_err());
+    assert!(Config::from_toml("[[device]]\ndev_eui = \"not hex\"").is_err());
+    assert!(Config::from_toml("[[device]]\ndev_eui = \"01\"\n[[device]]\ndev_eui = \"1\"").is_err());
+}
+
+#[test]
+fn watcher() {
+    let path = std::env::temp_dir().join(format!("elsys_test_config_{}.toml", std::process::id()));
+    let touch = |contents: &str, modified_s: u64| {
+        std::fs::write(&path, contents).unwrap();
+        let file = std::fs::File::options().write(true).open(&path).unwrap();
+        file.set_modified(std::time::UNIX_EPOCH + std::time::Duration::from_secs(modified_s)).unwrap();
+    };
+    let mut watcher = Watcher::new(&path);
+
+    assert!(watcher.poll().is_err());
+    touch("[limits]\nmax_records = 8", 1_000);
+    assert_eq!(8, watcher.poll().unwrap().unwrap().limits.max_records);
+    assert!(watcher.poll().unwrap().is_none());
+    touch("[limits]\nmax_records = -1", 2_000);
+    assert!(watcher.poll().is_err());
+    assert!(watcher.poll().unwrap().is_none());
+    touch("[limits]\nmax_records = 16", 3_000);
+    assert_eq!(16, watcher.poll().unwrap().unwrap().limits.max_records);
+
+    std::fs::remove_file(&path).unwrap();
+}
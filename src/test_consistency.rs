@@ -0,0 +1,34 @@
+use super::*;
+
+const NOON_S: u64 = 12 * HOUR_S;
+
+fn occupied(motion: u8) -> Uplink {
+    Uplink { occupancy: Some(Occupancy::OccupiedOrHeat), motion: Some(motion), ..Uplink::default() }
+}
+
+#[test]
+fn occupied_without_motion() {
+    let mut checker = Checker::new(ConsistencyConfig::default());
+
+    assert!(checker.push(0, &occupied(0)).is_empty());
+    assert!(checker.push(4 * HOUR_S, &occupied(0)).is_empty());
+    assert!(checker.push(4 * HOUR_S + 300, &Uplink { occupancy: Some(Occupancy::OccupiedOrHeat), ..Uplink::default() }).is_empty());
+    assert_eq!(vec![Diagnostic::OccupiedWithoutMotion { since_s: 0, still_s: 5 * HOUR_S }], checker.push(5 * HOUR_S, &occupied(0)));
+    assert!(checker.push(6 * HOUR_S, &occupied(0)).is_empty());
+
+    assert!(checker.push(7 * HOUR_S, &occupied(2)).is_empty());
+    assert!(checker.push(8 * HOUR_S, &occupied(0)).is_empty());
+    assert!(checker.push(12 * HOUR_S, &Uplink { occupancy: Some(Occupancy::NoBody), motion: Some(0), ..Uplink::default() }).is_empty());
+    assert!(checker.push(13 * HOUR_S, &occupied(0)).is_empty());
+}
+
+#[test]
+fn dark_while_occupied() {
+    let mut checker = Checker::new(ConsistencyConfig { utc_offset_s: 2 * HOUR_S as i64, ..ConsistencyConfig::default() });
+    let dark = Uplink { light: Some(0), ..occupied(3) };
+
+    assert_eq!(vec![Diagnostic::DarkWhileOccupied { at_s: NOON_S - 2 * HOUR_S }], checker.push(NOON_S - 2 * HOUR_S, &dark));
+    assert!(checker.push(NOON_S, &dark).is_empty());
+    assert!(checker.push(NOON_S - 2 * HOUR_S, &Uplink { light: Some(40), ..occupied(3) }).is_empty());
+    assert!(checker.push(NOON_S - 2 * HOUR_S, &Uplink { light: Some(0), occupancy: Some(Occupancy::NoBody), ..Uplink::default() }).is_empty());
+}
@@ -0,0 +1,41 @@
+use super::*;
+
+const DEVICE_A: u64 = 0xa81758fffe000001;
+const DEVICE_B: u64 = 0xa81758fffe000002;
+const PAYLOAD: [u8; 3] = [0x01, 0x00, 0xdc];
+
+#[test]
+fn repeats_within_window() {
+    let mut dedup = Deduplicator::new(60);
+
+    assert!(dedup.push(1000, DEVICE_A, 7, &PAYLOAD));
+    assert!(!dedup.push(1001, DEVICE_A, 7, &PAYLOAD));
+    assert!(!dedup.push(1059, DEVICE_A, 7, &PAYLOAD));
+    assert!(dedup.push(1001, DEVICE_B, 7, &PAYLOAD));
+    assert!(dedup.push(1001, DEVICE_A, 8, &PAYLOAD));
+    assert!(dedup.push(1001, DEVICE_A, 7, &[0x01, 0x00, 0xdd]));
+}
+
+#[test]
+fn expires() {
+    let mut dedup = Deduplicator::new(60);
+
+    assert!(dedup.push(1000, DEVICE_A, 7, &PAYLOAD));
+    assert!(dedup.push(1010, DEVICE_B, 7, &PAYLOAD));
+    assert_eq!(2, dedup.len());
+
+    assert!(dedup.push(1060, DEVICE_A, 7, &PAYLOAD));
+    assert_eq!(2, dedup.len());
+    assert!(!dedup.push(1061, DEVICE_B, 7, &PAYLOAD));
+    assert!(dedup.push(1200, DEVICE_A, 9, &PAYLOAD));
+    assert_eq!(1, dedup.len());
+}
+
+#[test]
+fn deserialize_once() {
+    let mut dedup = Deduplicator::new(60);
+
+    assert_eq!(Some(22.0), dedup.deserialize(1000, DEVICE_A, 7, &PAYLOAD).unwrap().unwrap().temperature());
+    assert!(dedup.deserialize(1001, DEVICE_A, 7, &PAYLOAD).is_none());
+    assert!(dedup.deserialize(1001, DEVICE_A, 8, &[0x20]).unwrap().is_err());
+}
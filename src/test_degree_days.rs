@@ -0,0 +1,41 @@
+use super::*;
+
+fn temperature(timestamp_s: u64, temperature: f32) -> (u64, Uplink) {
+    (timestamp_s, Uplink { temperature: Some(temperature), ..Uplink::default() })
+}
+
+#[test]
+fn daily_means() {
+    let readings = [
+        temperature(0, 10.0), temperature(43_200, 14.0), (50_000, Uplink::default()),
+        temperature(DAY_S, 20.0), temperature(DAY_S + 3600, 24.0),
+        temperature(3 * DAY_S, 18.0),
+    ];
+
+    let days = daily(&readings, &DegreeDayConfig::default());
+    assert_eq!(vec![
+        DegreeDays { start_s: 0, mean_temperature: 12.0, heating: 6.0, cooling: 0.0 },
+        DegreeDays { start_s: DAY_S, mean_temperature: 22.0, heating: 0.0, cooling: 4.0 },
+        DegreeDays { start_s: 3 * DAY_S, mean_temperature: 18.0, heating: 0.0, cooling: 0.0 },
+    ], days);
+    assert_eq!((6.0, 4.0), total(&days));
+}
+
+#[test]
+fn separate_bases() {
+    let config = DegreeDayConfig { heating_base: 15.5, cooling_base: 22.0, ..DegreeDayConfig::default() };
+    let days = daily(&[temperature(0, 12.5), temperature(DAY_S, 18.0), temperature(2 * DAY_S, 25.0)], &config);
+
+    assert_eq!((3.0, 3.0), total(&days));
+}
+
+#[test]
+fn day_boundary() {
+    // Days starting at 05:00 UTC, local midnight at UTC-5.
+    let config = DegreeDayConfig { day_start_s: 5 * 3600, ..DegreeDayConfig::default() };
+    let readings = [temperature(DAY_S + 3600, 10.0), temperature(DAY_S + 6 * 3600, 20.0)];
+
+    let days = daily(&readings, &config);
+    assert_eq!(vec![5 * 3600, DAY_S + 5 * 3600], days.iter().map(|day| day.start_s).collect::<Vec<_>>());
+    assert_eq!((8.0, 2.0), total(&days));
+}
@@ -0,0 +1,79 @@
+use super::*;
+
+#[test]
+fn update_keeps_absent_fields() {
+    let mut state = DeviceState::new();
+    state.update(100, &Uplink { temperature: Some(21.5), co2: Some(600), ..Uplink::default() });
+    state.update(200, &Uplink { co2: Some(700), ..Uplink::default() });
+
+    assert_eq!(Some(200), state.last_uplink_s());
+    assert_eq!(Some(Timestamped { value: 21.5, timestamp_s: 100 }), state.temperature());
+    assert_eq!(Some(Timestamped { value: 700, timestamp_s: 200 }), state.co2_ppm());
+    assert_eq!(None, state.occupancy());
+}
+
+#[test]
+fn update_ignores_older_values() {
+    let mut state = DeviceState::new();
+    state.update(200, &Uplink { co2: Some(700), ..Uplink::default() });
+    state.update(100, &Uplink { co2: Some(600), external_digital: Some(true), ..Uplink::default() });
+
+    assert_eq!(Some(200), state.last_uplink_s());
+    assert_eq!(Some(Timestamped { value: 700, timestamp_s: 200 }), state.co2_ppm());
+    assert_eq!(Some(Timestamped { value: true, timestamp_s: 100 }), state.external_digital());
+}
+
+#[test]
+fn staleness() {
+    let mut state = DeviceState::new();
+    state.update(100, &Uplink { temperature: Some(21.5), battery_mv: Some(3600), ..Uplink::default() });
+    state.update(1000, &Uplink { temperature: Some(21.0), ..Uplink::default() });
+
+    assert!(!state.is_stale(Field::Temperature, 1500, 600));
+    assert!(state.is_stale(Field::Battery, 1500, 600));
+    assert!(!state.is_stale(Field::Co2, 1500, 600));
+    assert_eq!(vec![Field::Battery], state.stale_fields(1500, 600));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_round_trip() {
+    let mut state = DeviceState::new();
+    state.update(100, &Uplink { temperature: Some(21.5), occupancy: Some(Occupancy::NoBody), ..Uplink::default() });
+
+    let json = serde_json::to_string(&state).unwrap();
+    assert_eq!(state, serde_json::from_str(&json).unwrap());
+}
+
+#[test]
+fn latest_and_value() {
+    let mut state = DeviceState::new();
+    state.update(100, &Uplink { temperature: Some(21.5), occupancy: Some(Occupancy::OccupiedOrHeat), ..Uplink::default() });
+    state.update(200, &Uplink { co2: Some(700), ..Uplink::default() });
+
+    assert_eq!(
+        Uplink { temperature: Some(21.5), co2: Some(700), occupancy: Some(Occupancy::OccupiedOrHeat), ..Uplink::default() },
+        state.latest()
+    );
+    assert_eq!(Some(Timestamped { value: 2.0, timestamp_s: 100 }), state.value(Field::Occupancy));
+    assert_eq!(Some(Timestamped { value: 700.0, timestamp_s: 200 }), state.value(Field::Co2));
+    assert_eq!(None, state.value(Field::Battery));
+}
+
+#[test]
+fn acceleration() {
+    let mut state = DeviceState::new();
+    state.update(100, &Uplink { acceleration: Some([63, 0, -63]), ..Uplink::default() });
+
+    assert_eq!(Some(Timestamped { value: Acceleration { x: 1.0, y: 0.0, z: -1.0 }, timestamp_s: 100 }), state.acceleration());
+    assert_eq!(Some([63, 0, -63]), state.latest().acceleration_raw());
+}
+
+#[test]
+fn gps() {
+    let mut state = DeviceState::new();
+    state.update(100, &Uplink { gps: Some([638258, 202630]), ..Uplink::default() });
+    state.update(200, &Uplink { co2: Some(600), ..Uplink::default() });
+
+    assert_eq!(Some(Timestamped { value: GpsPosition { latitude: 63.8258, longitude: 20.263 }, timestamp_s: 100 }), state.gps());
+}
@@ -0,0 +1,37 @@
+use super::*;
+
+#[test]
+fn fields() {
+    let lua = lua();
+
+    assert!(lua.contains("fields[0x01] = ProtoField.float(\"elsys.temperature\", \"Temperature\")\n"));
+    assert!(lua.contains("values[0x01] = function(range) return range:int() / 10 end\n"));
+    assert!(lua.contains("units[0x01] = \" °C\"\n"));
+    assert!(lua.contains("fields[0x07] = ProtoField.uint16(\"elsys.battery\", \"Battery\")\n"));
+    assert!(lua.contains("units[0x07] = \" mV\"\n"));
+    assert!(lua.contains("fields[0x09] = ProtoField.string(\"elsys.gps\", \"GPS\")\n"));
+    assert!(lua.contains("range(0, 3):le_int() / 10000"));
+    assert!(lua.contains("fields[0x0c] = ProtoField.float(\"elsys.external_temperature_1\", \"External Temperature 1\")\n"));
+    assert!(lua.contains("fields[0x1b] = ProtoField.int32(\"elsys.external_analog\", \"External Analog\")\n"));
+    assert!(lua.contains("fields[0x13] = ProtoField.bytes(\"elsys.grideye\", \"Grideye\")\n"));
+    assert!(lua.contains("sizes[0x13] = 65\n"));
+    assert!(lua.contains("function elsys.dissector(buffer, pinfo, tree)"));
+}
+
+#[test]
+fn every_record() {
+    let lua = lua();
+
+    for (identifier, size, name) in records() {
+        assert!(lua.contains(&format!("names[0x{:02x}] = \"{}\"\n", identifier, name)));
+        assert!(lua.contains(&format!("sizes[0x{:02x}] = {}\n", identifier, size)));
+    }
+}
+
+#[test]
+fn every_record_has_a_format() {
+    for (identifier, _, name) in records() {
+        let bytes = matches!(format(identifier), Format::Bytes);
+        assert_eq!(matches!(identifier, 0x13 | 0x3d), bytes, "{}", name);
+    }
+}
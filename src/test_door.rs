@@ -0,0 +1,56 @@
+use super::*;
+
+fn digital(state: bool) -> Uplink {
+    Uplink { external_digital: Some(state), ..Uplink::default() }
+}
+
+#[test]
+fn openings() {
+    let mut tracker = DoorTracker::new(DoorConfig { open_when: true, max_open_s: 600 });
+
+    assert_eq!(None, tracker.push(0, 1, &digital(false)));
+    assert_eq!(Some(false), tracker.is_open());
+    assert_eq!(Some(Event::Opened { at_s: 60 }), tracker.push(60, 2, &digital(true)));
+    assert_eq!(None, tracker.push(300, 3, &digital(true)));
+    assert_eq!(Some(Event::Closed { at_s: 360, open_s: 300 }), tracker.push(360, 4, &digital(false)));
+    tracker.push(400, 5, &digital(true));
+    tracker.push(500, 6, &digital(false));
+
+    assert_eq!(DoorStats { openings: 2, open_s: 400, longest_open_s: 300, too_long: 0 }, tracker.stats());
+}
+
+#[test]
+fn open_too_long() {
+    let mut tracker = DoorTracker::new(DoorConfig { open_when: false, max_open_s: 600 });
+
+    tracker.push(0, 1, &digital(true));
+    tracker.push(60, 2, &digital(false));
+    assert_eq!(Some(true), tracker.is_open());
+    assert_eq!(None, tracker.push(660, 3, &digital(false)));
+    assert_eq!(Some(Event::OpenTooLong { since_s: 60, open_s: 601 }), tracker.push(661, 4, &digital(false)));
+    assert_eq!(None, tracker.push(1000, 5, &Uplink::default()));
+    assert_eq!(None, tracker.push(1200, 6, &digital(false)));
+    assert_eq!(Some(Event::Closed { at_s: 1260, open_s: 1200 }), tracker.push(1260, 7, &digital(true)));
+    assert_eq!(1, tracker.stats().too_long);
+}
+
+#[test]
+fn open_at_first_uplink() {
+    let mut tracker = DoorTracker::new(DoorConfig { open_when: true, max_open_s: 600 });
+
+    assert_eq!(None, tracker.push(0, 1, &Uplink::default()));
+    assert_eq!(Some(Event::Opened { at_s: 60 }), tracker.push(60, 2, &digital(true)));
+    assert_eq!(None, tracker.push(600, 3, &digital(true)));
+    assert_eq!(Some(Event::OpenTooLong { since_s: 60, open_s: 601 }), tracker.push(661, 4, &digital(true)));
+    assert_eq!(Some(Event::Closed { at_s: 700, open_s: 640 }), tracker.push(700, 5, &digital(false)));
+    assert_eq!(DoorStats { openings: 1, open_s: 640, longest_open_s: 640, too_long: 1 }, tracker.stats());
+}
+
+#[test]
+fn closed_at_first_uplink() {
+    let mut tracker = DoorTracker::new(DoorConfig { open_when: true, max_open_s: 600 });
+
+    assert_eq!(None, tracker.push(0, 1, &digital(false)));
+    assert_eq!(None, tracker.push(60, 2, &digital(false)));
+    assert_eq!(DoorStats::default(), tracker.stats());
+}
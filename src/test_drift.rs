@@ -0,0 +1,45 @@
+use super::*;
+
+fn co2_history(floor: u16) -> Vec<(u64, Uplink)> {
+    (0..100)
+        .map(|i| (i * 600, Uplink { co2: Some(floor + (i as u16 % 10) * 60), ..Uplink::default() }))
+        .collect()
+}
+
+#[test]
+fn test_baseline() {
+    assert_eq!(Some(420.0), baseline(&co2_history(420), Field::Co2, 0.05));
+    assert_eq!(None, baseline(&co2_history(420), Field::Temperature, 0.05));
+}
+
+#[test]
+fn detects_co2_creep_against_fleet() {
+    let fleet = [420.0, 410.0, 430.0];
+    let reference = crate::aggregate::quantile(&fleet, 0.5).unwrap();
+    let device = baseline(&co2_history(500), Field::Co2, 0.05).unwrap();
+
+    let drift = detect(Field::Co2, device, reference, 50.0).unwrap();
+    assert_eq!(80.0, drift.deviation());
+    assert_eq!(Some(-80.0), drift.recommended_calibration().map(|c| c.co2_offset_ppm));
+    assert_eq!(None, detect(Field::Co2, 440.0, reference, 50.0));
+}
+
+#[test]
+fn recommended_calibration_corrects_baseline() {
+    let drift = detect(Field::Temperature, 21.0, 22.5, 1.0).unwrap();
+    let mut uplink = Uplink { temperature: Some(21.0), ..Uplink::default() };
+    uplink.apply_calibration(&drift.recommended_calibration().unwrap());
+
+    assert_eq!(Some(22.5), uplink.temperature());
+
+    let drift = detect(Field::Humidity, 40.0, 45.0, 2.0).unwrap();
+    let mut uplink = Uplink { humidity: Some(40), ..Uplink::default() };
+    uplink.apply_calibration(&drift.recommended_calibration().unwrap());
+    assert_eq!(Some(45), uplink.humidity_percent());
+
+    let drift = detect(Field::ExternalTemperature2, -18.0, -20.0, 1.0).unwrap();
+    let mut uplink = Uplink { external_temperature_2: Some(-18.0), ..Uplink::default() };
+    uplink.apply_calibration(&drift.recommended_calibration().unwrap());
+    assert_eq!(Some(-20.0), uplink.external_temperature_2);
+    assert_eq!(None, detect(Field::Battery, 3.0, 3.6, 0.1).unwrap().recommended_calibration());
+}
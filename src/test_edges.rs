@@ -0,0 +1,46 @@
+use super::*;
+
+fn digital(state: bool) -> Uplink {
+    Uplink { external_digital: Some(state), ..Uplink::default() }
+}
+
+#[test]
+fn rising_and_falling() {
+    let mut detector = EdgeDetector::new();
+
+    assert_eq!(None, detector.push(0, 1, &digital(false)));
+    assert_eq!(None, detector.push(60, 2, &digital(false)));
+    assert_eq!(
+        Some(Edge { direction: Direction::Rising, previous_s: 60, timestamp_s: 120, missed_frames: 0 }),
+        detector.push(120, 3, &digital(true))
+    );
+    assert_eq!(Some(true), detector.state());
+    assert_eq!(Direction::Falling, detector.push(180, 4, &digital(false)).unwrap().direction);
+}
+
+#[test]
+fn missed_frames() {
+    let mut detector = EdgeDetector::new();
+
+    detector.push(0, 10, &digital(false));
+    assert_eq!(3, detector.push(240, 14, &digital(true)).unwrap().missed_frames);
+}
+
+#[test]
+fn frame_counter_reset() {
+    let mut detector = EdgeDetector::new();
+
+    detector.push(0, u32::MAX - 1, &digital(false));
+    assert_eq!(0, detector.push(240, 2, &digital(true)).unwrap().missed_frames);
+    assert_eq!(1, detector.push(300, 4, &digital(false)).unwrap().missed_frames);
+}
+
+#[test]
+fn uplinks_without_digital_are_ignored() {
+    let mut detector = EdgeDetector::new();
+
+    detector.push(0, 1, &digital(true));
+    assert_eq!(None, detector.push(60, 2, &Uplink::default()));
+    assert_eq!(Some(true), detector.state());
+    assert_eq!(1, detector.push(180, 4, &digital(false)).unwrap().missed_frames);
+}
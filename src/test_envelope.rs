@@ -0,0 +1,48 @@
+use super::*;
+
+const CHIRPSTACK: &str = r#"{"deviceInfo":{"devEui":"a81758fffe000001"},"time":"2024-03-01T12:34:56.789Z","fPort":5,"data":"AQDcBgJY","rxInfo":[{"gatewayId":"gw-1","rssi":-97}]}"#;
+
+fn chirpstack() -> Envelope {
+    Envelope {
+        dev_eui: "$.deviceInfo.devEui".to_string(),
+        payload: "$.data".to_string(),
+        encoding: Encoding::Base64,
+        port: Some("$.fPort".to_string()),
+        timestamp: "$.time".to_string(),
+        time_format: TimeFormat::Rfc3339,
+    }
+}
+
+#[test]
+fn extract() {
+    assert_eq!(
+        Some(Extracted { record: Record { timestamp_s: 1_709_296_496, dev_eui: 0xa81758fffe000001, payload: vec![0x01, 0x00, 0xdc, 0x06, 0x02, 0x58] }, port: Some(5) }),
+        chirpstack().extract(CHIRPSTACK).unwrap()
+    );
+}
+
+#[test]
+fn bespoke() {
+    let envelope: Envelope = serde_json::from_str(r#"{"dev_eui":"device.0","payload":"uplink.hex","encoding":"hex","timestamp":"uplink.at_ms","time_format":"unix_milliseconds"}"#).unwrap();
+    let message = r#"{"device":["A81758FFFE000002"],"uplink":{"hex":"1102","at_ms":1709296496789}}"#;
+
+    assert_eq!(
+        Some(Extracted { record: Record { timestamp_s: 1_709_296_496, dev_eui: 0xa81758fffe000002, payload: vec![0x11, 0x02] }, port: None }),
+        envelope.extract(message).unwrap()
+    );
+}
+
+#[test]
+fn without_payload() {
+    assert_eq!(None, chirpstack().extract(r#"{"deviceInfo":{"devEui":"a81758fffe000001"},"time":"2024-03-01T12:34:56Z"}"#).unwrap());
+    assert_eq!(None, chirpstack().extract(r#"{"data":null}"#).unwrap());
+}
+
+#[test]
+fn invalid() {
+    assert!(chirpstack().extract("not json").is_err());
+    assert!(chirpstack().extract(&CHIRPSTACK.replace("a81758fffe000001", "nope")).is_err());
+    assert!(chirpstack().extract(&CHIRPSTACK.replace("AQDcBgJY", "!!")).is_err());
+    assert!(chirpstack().extract(&CHIRPSTACK.replace("\"fPort\":5", "\"fPort\":500")).is_err());
+    assert!(chirpstack().extract(&CHIRPSTACK.replace("2024-03-01T12:34:56.789Z", "yesterday")).is_err());
+}
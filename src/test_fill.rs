@@ -0,0 +1,64 @@
+use super::*;
+use crate::utilization::HOUR_S;
+
+const BIN: FillConfig = FillConfig { empty_mm: 1100, full_mm: 100, emptied_drop_percent: 30.0 };
+
+fn reading(timestamp_s: u64, distance_mm: u16) -> (u64, Uplink) {
+    (timestamp_s, Uplink { distance: Some(distance_mm), ..Uplink::default() })
+}
+
+#[test]
+fn levels() {
+    assert_eq!(Some(0.0), BIN.level_percent(1100));
+    assert_eq!(Some(50.0), BIN.level_percent(600));
+    assert_eq!(Some(100.0), BIN.level_percent(50));
+    assert_eq!(Some(0.0), BIN.level_percent(1500));
+    assert_eq!(None, FillConfig { empty_mm: 100, ..BIN }.level_percent(100));
+}
+
+#[test]
+fn linear_filling() {
+    // 5 % an hour, 40 % full at the latest reading.
+    let readings = (0..=8).map(|hour| reading(hour * HOUR_S, 1100 - 50 * hour as u16)).collect::<Vec<_>>();
+
+    for fit in [Fit::LeastSquares, Fit::TheilSen] {
+        let time_s = BIN.time_to_fill_s(&readings, fit, 80.0).unwrap();
+        assert!(time_s.abs_diff(8 * HOUR_S) < 10, "{:?}: {}", fit, time_s);
+        assert_eq!(Some(0), BIN.time_to_fill_s(&readings, fit, 40.0));
+    }
+}
+
+#[test]
+fn theil_sen_ignores_outliers() {
+    let mut readings = (0..=8).map(|hour| reading(hour * HOUR_S, 1100 - 50 * hour as u16)).collect::<Vec<_>>();
+    readings[7] = reading(7 * HOUR_S, 1050);
+
+    let robust_s = BIN.time_to_fill_s(&readings, Fit::TheilSen, 80.0).unwrap();
+    assert!(robust_s.abs_diff(8 * HOUR_S) < 10);
+    let least_squares_s = BIN.time_to_fill_s(&readings, Fit::LeastSquares, 80.0).unwrap();
+    assert!(least_squares_s.abs_diff(8 * HOUR_S) > HOUR_S);
+}
+
+#[test]
+fn restarts_after_emptying() {
+    let readings = [
+        reading(0, 300),
+        reading(HOUR_S, 250),
+        reading(2 * HOUR_S, 1100),
+        reading(3 * HOUR_S, 1000),
+        reading(4 * HOUR_S, 900),
+    ];
+
+    assert_eq!(3, BIN.since_emptied(&readings).len());
+    let time_s = BIN.time_to_fill_s(&readings, Fit::LeastSquares, 50.0).unwrap();
+    assert!(time_s.abs_diff(3 * HOUR_S) < 10);
+}
+
+#[test]
+fn not_filling() {
+    let steady = [reading(0, 600), reading(HOUR_S, 600)];
+    assert_eq!(None, BIN.time_to_fill_s(&steady, Fit::LeastSquares, 80.0));
+    assert_eq!(None, BIN.time_to_fill_s(&steady, Fit::TheilSen, 80.0));
+    assert_eq!(None, BIN.time_to_fill_s(&steady[..1], Fit::TheilSen, 80.0));
+    assert_eq!(None, BIN.time_to_fill_s(&[], Fit::LeastSquares, 80.0));
+}
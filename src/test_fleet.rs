@@ -0,0 +1,59 @@
+use super::*;
+
+fn fleet() -> Fleet {
+    let mut fleet = Fleet::new();
+    fleet.update(3, 1000, &Uplink { temperature: Some(21.0), battery_mv: Some(3650), ..Uplink::default() });
+    fleet.update(1, 100, &Uplink { temperature: Some(19.0), battery_mv: Some(2900), ..Uplink::default() });
+    fleet.update(2, 900, &Uplink { temperature: Some(23.0), battery_mv: Some(3400), ..Uplink::default() });
+    fleet.update(4, 50, &Uplink { battery_mv: Some(3600), ..Uplink::default() });
+    fleet
+}
+
+#[test]
+fn silent_devices() {
+    assert_eq!(vec![1, 4], fleet().silent(1000, 600));
+    assert!(fleet().silent(1000, 1000).is_empty());
+}
+
+#[test]
+fn percentiles() {
+    let fleet = fleet();
+
+    assert_eq!(Some(21.0), fleet.percentile(Field::Temperature, 0.5));
+    assert_eq!(Some(23.0), fleet.percentile(Field::Temperature, 1.0));
+    assert_eq!(None, fleet.percentile(Field::Co2, 0.5));
+}
+
+#[test]
+fn battery_histogram() {
+    assert_eq!(vec![1, 1, 2], fleet().battery_histogram(&[3.0, 3.5]));
+}
+
+#[test]
+fn summary() {
+    let summary = fleet().summary(1000, 600);
+
+    assert_eq!(4, summary.devices);
+    assert_eq!(vec![1, 4], summary.silent);
+    assert_eq!(vec![Field::Temperature, Field::Battery], summary.fields.iter().map(|f| f.field).collect::<Vec<_>>());
+    assert_eq!(
+        FieldSummary { field: Field::Temperature, devices: 3, min: 19.0, p10: 19.0, median: 21.0, p90: 23.0, max: 23.0 },
+        summary.fields[0]
+    );
+}
+
+#[test]
+fn activity() {
+    let mut fleet = Fleet::new();
+    assert!(fleet.deserialize(7, 200, &[0x07, 0x0e, 0x10]).is_ok());
+    assert!(fleet.deserialize(7, 100, &[0x07, 0x0e]).is_err());
+    assert!(fleet.deserialize(8, 300, &[0xff]).is_err());
+
+    assert_eq!(
+        Some(&Activity { first_s: Some(100), last_s: Some(200), uplinks: 1, errors: 1 }),
+        fleet.device_activity(7)
+    );
+    assert_eq!(Some(3600), fleet.device(7).unwrap().latest().battery_mv);
+    assert_eq!(1, fleet.device_activity(8).unwrap().errors);
+    assert!(fleet.device(8).is_none());
+}
@@ -0,0 +1,52 @@
+use super::*;
+
+fn co2(co2: u16) -> Uplink {
+    Uplink { co2: Some(co2), ..Uplink::default() }
+}
+
+fn close(expected: f32, actual: f32) -> bool {
+    (expected - actual).abs() < 0.5
+}
+
+#[test]
+fn linear_rise() {
+    let mut forecaster = Forecaster::new(Field::Co2, 0.5, 0.3);
+    assert_eq!(None, forecaster.forecast(1800));
+
+    for i in 0..30 {
+        forecaster.push(i * 600, &co2(500 + i as u16 * 50));
+    }
+
+    assert!(close(300.0, forecaster.trend_per_hour().unwrap()));
+    assert!(close(1950.0 + 150.0, forecaster.forecast(1800).unwrap()));
+    assert!(close(1950.0 + 300.0, forecaster.forecast(3600).unwrap()));
+    assert!(close(1950.0, forecaster.forecast(0).unwrap()));
+
+    let time_s = forecaster.time_to_threshold_s(2100.0).unwrap();
+    assert!((1790..=1810).contains(&time_s));
+    assert_eq!(None, forecaster.time_to_threshold_s(1000.0));
+}
+
+#[test]
+fn ewma() {
+    let mut forecaster = Forecaster::new(Field::Co2, 0.5, 0.0);
+
+    forecaster.push(0, &co2(400));
+    forecaster.push(600, &co2(800));
+    forecaster.push(1200, &co2(800));
+
+    assert_eq!(Some(700.0), forecaster.forecast(3600));
+    assert_eq!(Some(0.0), forecaster.trend_per_hour());
+    assert_eq!(None, forecaster.time_to_threshold_s(1000.0));
+}
+
+#[test]
+fn ignores_other_fields_and_repeated_timestamps() {
+    let mut forecaster = Forecaster::new(Field::Co2, 0.5, 0.5);
+
+    forecaster.push(0, &co2(400));
+    forecaster.push(0, &co2(1000));
+    forecaster.push(300, &Uplink { temperature: Some(21.0), ..Uplink::default() });
+
+    assert_eq!(Some(400.0), forecaster.forecast(600));
+}
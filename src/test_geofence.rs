@@ -0,0 +1,60 @@
+use super::*;
+
+const CENTRE: GpsPosition = GpsPosition { latitude: 57.7089, longitude: 11.9746 };
+
+fn at(latitude: f64, longitude: f64) -> Uplink {
+    Uplink { gps: Some([(latitude * 10_000.0).round() as i32, (longitude * 10_000.0).round() as i32]), ..Uplink::default() }
+}
+
+#[test]
+fn distance() {
+    let london = GpsPosition { latitude: 51.5074, longitude: -0.1278 };
+    let paris = GpsPosition { latitude: 48.8566, longitude: 2.3522 };
+
+    assert!((london.distance_m(&paris) - 343_560.0).abs() < 500.0);
+    assert_eq!(0.0, CENTRE.distance_m(&CENTRE));
+}
+
+#[test]
+fn circle() {
+    let geofence = Geofence::Circle { centre: CENTRE, radius_m: 200.0 };
+
+    assert!(GpsPosition { latitude: 57.7100, longitude: 11.9746 }.inside(&geofence));
+    assert!(!GpsPosition { latitude: 57.7110, longitude: 11.9746 }.inside(&geofence));
+}
+
+#[test]
+fn polygon() {
+    let geofence = Geofence::Polygon(vec![
+        GpsPosition { latitude: 0.0, longitude: 0.0 },
+        GpsPosition { latitude: 0.0, longitude: 2.0 },
+        GpsPosition { latitude: 1.0, longitude: 1.0 },
+        GpsPosition { latitude: 2.0, longitude: 2.0 },
+        GpsPosition { latitude: 2.0, longitude: 0.0 },
+    ]);
+
+    assert!(GpsPosition { latitude: 0.5, longitude: 1.0 }.inside(&geofence));
+    assert!(GpsPosition { latitude: 1.0, longitude: 0.5 }.inside(&geofence));
+    assert!(!GpsPosition { latitude: 1.0, longitude: 1.5 }.inside(&geofence));
+    assert!(!GpsPosition { latitude: 3.0, longitude: 1.0 }.inside(&geofence));
+    assert!(!GpsPosition { latitude: 0.5, longitude: 0.5 }.inside(&Geofence::Polygon(Vec::new())));
+}
+
+#[test]
+fn enter_and_exit() {
+    let mut tracker = GeofenceTracker::new(Geofence::Circle { centre: CENTRE, radius_m: 200.0 });
+
+    assert_eq!(None, tracker.push(0, &at(57.7200, 11.9746)));
+    assert_eq!(Some(false), tracker.inside());
+    assert_eq!(Some(Event::Entered { at_s: 600 }), tracker.push(600, &at(57.7090, 11.9750)));
+    assert_eq!(None, tracker.push(900, &Uplink::default()));
+    assert_eq!(None, tracker.push(1200, &at(57.7085, 11.9740)));
+    assert_eq!(Some(Event::Exited { at_s: 1800 }), tracker.push(1800, &at(57.7000, 11.9746)));
+}
+
+#[test]
+fn inside_at_first_position() {
+    let mut tracker = GeofenceTracker::new(Geofence::Circle { centre: CENTRE, radius_m: 200.0 });
+
+    assert_eq!(Some(Event::Entered { at_s: 0 }), tracker.push(0, &at(57.7089, 11.9746)));
+}
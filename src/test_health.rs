@@ -0,0 +1,55 @@
+use super::*;
+use crate::Uplink;
+
+#[test]
+fn healthy_device() {
+    let mut fleet = Fleet::new();
+    for i in 0..6 {
+        fleet.update(1, i * 600, &Uplink { battery_mv: Some(3650), ..Uplink::default() });
+    }
+
+    assert_eq!(Some(100), fleet.health(1, &HealthConfig::default(), 3000));
+    assert_eq!(None, fleet.health(2, &HealthConfig::default(), 3000));
+}
+
+#[test]
+fn components() {
+    let activity = Activity { first_s: Some(0), last_s: Some(5400), uplinks: 4, errors: 1 };
+
+    // battery 0.5, regularity 5 of 10, errors 4 of 5
+    assert_eq!(Some(60), score(&HealthConfig::default(), Some(3.3), &activity, 5400));
+    assert_eq!(Some(65), score(&HealthConfig::default(), None, &activity, 5400));
+}
+
+#[test]
+fn weights() {
+    let activity = Activity { first_s: Some(0), last_s: Some(5400), uplinks: 4, errors: 1 };
+    let config = HealthConfig {
+        weights: Weights { battery: 0.0, regularity: 0.0, errors: 1.0 },
+        ..HealthConfig::default()
+    };
+
+    assert_eq!(Some(80), score(&config, Some(3.3), &activity, 5400));
+}
+
+#[test]
+fn silence_lowers_regularity() {
+    let activity = Activity { first_s: Some(0), last_s: Some(600), uplinks: 2, errors: 0 };
+    let config = HealthConfig {
+        weights: Weights { battery: 0.0, regularity: 1.0, errors: 0.0 },
+        ..HealthConfig::default()
+    };
+
+    assert_eq!(Some(100), score(&config, None, &activity, 600));
+    assert_eq!(Some(20), score(&config, None, &activity, 5400));
+}
+
+#[test]
+fn empty_battery_range() {
+    let activity = Activity { first_s: Some(0), last_s: Some(5400), uplinks: 4, errors: 1 };
+    let config = HealthConfig { battery_empty_v: 3.6, battery_full_v: 3.6, ..HealthConfig::default() };
+
+    assert_eq!(score(&config, None, &activity, 5400), score(&config, Some(3.3), &activity, 5400));
+    let config = HealthConfig { battery_empty_v: 3.6, battery_full_v: 3.0, ..HealthConfig::default() };
+    assert_eq!(Some(65), score(&config, Some(3.3), &activity, 5400));
+}
@@ -0,0 +1,20 @@
+use super::*;
+
+#[test]
+fn track_with_placemarks() {
+    let readings = [
+        (1_709_510_400, Uplink { gps: Some([577_089, 119_746]), temperature: Some(4.5), ..Uplink::default() }),
+        (1_709_510_700, Uplink { temperature: Some(4.6), ..Uplink::default() }),
+        (1_709_511_000, Uplink { gps: Some([577_100, 119_800]), ..Uplink::default() }),
+    ];
+
+    let kml = track("Truck <7> & trailer", &readings);
+    assert!(kml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<kml xmlns=\"http://www.opengis.net/kml/2.2\">\n"));
+    assert!(kml.contains("<name>Truck &lt;7&gt; &amp; trailer</name>\n"));
+    assert!(kml.contains("<coordinates>\n11.9746,57.7089\n11.9800,57.7100\n</coordinates>\n"));
+    assert!(kml.contains("<TimeStamp><when>2024-03-04T00:00:00Z</when></TimeStamp>\n<description>temperature: 4.5\ngps: 57.7089 11.9746</description>\n<Point><coordinates>11.9746,57.7089</coordinates></Point>\n"));
+    assert!(kml.contains("<when>2024-03-04T00:10:00Z</when>"));
+    assert!(!kml.contains("4.6"));
+    assert_eq!(3, kml.matches("<Placemark>").count());
+    assert!(kml.ends_with("</Document>\n</kml>\n"));
+}
@@ -0,0 +1,32 @@
+use super::*;
+
+fn leak(level: u8) -> Uplink {
+    Uplink { waterleak: Some(level), ..Uplink::default() }
+}
+
+#[test]
+fn latches_until_acknowledged() {
+    let mut alarm = LeakAlarm::new(LeakConfig { threshold: 10, suppress_s: 600 });
+
+    assert_eq!(None, alarm.push(0, &leak(5)));
+    assert_eq!(Some(Event::Latched { at_s: 60 }), alarm.push(60, &leak(200)));
+    assert_eq!(None, alarm.push(120, &leak(200)));
+    assert_eq!(None, alarm.push(180, &leak(0)));
+    assert_eq!(None, alarm.push(240, &Uplink::default()));
+    assert_eq!(Some(60), alarm.latched());
+
+    assert_eq!(Some(Event::Acknowledged { since_s: 60, at_s: 300 }), alarm.acknowledge(300));
+    assert_eq!(None, alarm.latched());
+    assert_eq!(None, alarm.acknowledge(310));
+}
+
+#[test]
+fn suppresses_retriggers_after_acknowledgement() {
+    let mut alarm = LeakAlarm::new(LeakConfig { threshold: 0, suppress_s: 600 });
+
+    alarm.push(0, &leak(1));
+    alarm.acknowledge(100);
+    assert_eq!(None, alarm.push(200, &leak(1)));
+    assert_eq!(None, alarm.push(699, &leak(1)));
+    assert_eq!(Some(Event::Latched { at_s: 700 }), alarm.push(700, &leak(1)));
+}
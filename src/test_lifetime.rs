@@ -0,0 +1,35 @@
+use super::*;
+
+const YEAR_S: u64 = 365 * 24 * 3600;
+
+fn consumption() -> Consumption {
+    Consumption { sleep_ua: 5.0, transmit_ma: 40.0, sample_uas: vec![(Field::Temperature, 60.0), (Field::Co2, 6000.0)] }
+}
+
+fn profile(send_period_s: u64, spreading_factor: u8) -> Profile {
+    Profile { sample_period_s: 600, send_period_s, fields: vec![Field::Temperature, Field::Battery], modulation: Modulation::lorawan(spreading_factor, 125_000) }
+}
+
+#[test]
+fn payload_len() {
+    assert_eq!(6, profile(600, 7).payload_len());
+}
+
+#[test]
+fn average_current() {
+    // 51.456 ms on air every 600 s at 40 mA, and 60 uAs every 600 s.
+    let expected_ua = 5.0 + 40_000.0 * 0.051456 / 600.0 + 60.0 / 600.0;
+    assert!((expected_ua - average_current_ua(&consumption(), &profile(600, 7)).unwrap()).abs() < 1e-9);
+}
+
+#[test]
+fn battery_life() {
+    let sf7 = battery_life_s(2600.0, &consumption(), &profile(600, 7)).unwrap();
+    let sf12 = battery_life_s(2600.0, &consumption(), &profile(600, 12)).unwrap();
+    let hourly = battery_life_s(2600.0, &consumption(), &profile(3600, 12)).unwrap();
+
+    assert!(sf7 > 20 * YEAR_S);
+    assert!(sf12 < sf7);
+    assert!(hourly > sf12);
+    assert_eq!(None, battery_life_s(2600.0, &consumption(), &profile(600, 13)));
+}
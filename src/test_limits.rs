@@ -0,0 +1,43 @@
+use super::*;
+
+fn limits_exceeded(result: Result<Uplink>) -> LimitsExceeded {
+    let error = result.unwrap_err();
+    assert_eq!(ErrorKind::InvalidData, error.kind());
+    *error.get_ref().unwrap().downcast_ref::<LimitsExceeded>().unwrap()
+}
+
+#[test]
+fn within_limits() {
+    let input = [0x01, 0x00, 0xdc, 0x07, 0x0e, 0x41];
+
+    assert_eq!(Uplink::deserialize(&input).unwrap(), Uplink::deserialize_with_limits(&input, &Limits::default()).unwrap());
+}
+
+#[test]
+fn payload_bytes() {
+    let limits = Limits { max_payload_bytes: 5, ..Limits::default() };
+
+    assert_eq!(LimitsExceeded::PayloadBytes { limit: 5, actual: 6 }, limits_exceeded(Uplink::deserialize_with_limits(&[0x01, 0x00, 0xdc, 0x07, 0x0e, 0x41], &limits)));
+}
+
+#[test]
+fn records() {
+    let limits = Limits { max_records: 2, ..Limits::default() };
+
+    assert!(Uplink::deserialize_with_limits(&[0x11, 0x00, 0x0d, 0x01], &limits).is_ok());
+    assert_eq!(LimitsExceeded::Records { limit: 2 }, limits_exceeded(Uplink::deserialize_with_limits(&[0x11, 0x00, 0x0d, 0x01, 0x11, 0x02], &limits)));
+}
+
+#[test]
+fn repeats() {
+    let limits = Limits { max_repeats: 1, ..Limits::default() };
+
+    assert_eq!(LimitsExceeded::Repeats { identifier: 0x11, limit: 1 }, limits_exceeded(Uplink::deserialize_with_limits(&[0x11, 0x00, 0x0d, 0x01, 0x11, 0x02], &limits)));
+}
+
+#[test]
+fn malformed_is_not_limits_exceeded() {
+    let error = Uplink::deserialize_with_limits(&[0x20], &Limits::default()).unwrap_err();
+
+    assert!(error.get_ref().unwrap().downcast_ref::<LimitsExceeded>().is_none());
+}
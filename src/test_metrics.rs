@@ -0,0 +1,36 @@
+use super::*;
+
+#[test]
+fn counts() {
+    let metrics = Metrics::new();
+
+    assert!(metrics.deserialize(&[0x01, 0x00, 0xdc, 0x07, 0x0e, 0x41]).is_ok());
+    assert!(metrics.deserialize(&[0x01, 0x00, 0xdd, 0x11, 0x00]).is_ok());
+    assert!(metrics.deserialize(&[0x01, 0x00]).is_err());
+    assert!(metrics.deserialize(&[0x20]).is_err());
+
+    let snapshot = metrics.snapshot();
+    assert_eq!(2, snapshot.successes);
+    assert_eq!(2, snapshot.failures);
+    assert_eq!(vec![(0x01, 2), (0x07, 1), (0x11, 1)], snapshot.identifiers);
+    assert_eq!(LATENCY_BUCKETS_NS.len() + 1, snapshot.latency.len());
+    assert_eq!(4, snapshot.latency.iter().sum::<u64>());
+}
+
+#[test]
+fn shared_between_threads() {
+    let metrics = Metrics::new();
+
+    std::thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                for _ in 0..100 {
+                    metrics.deserialize(&[0x06, 0x02, 0x58]).unwrap();
+                }
+            });
+        }
+    });
+
+    assert_eq!(400, metrics.snapshot().successes);
+    assert_eq!(vec![(0x06, 400)], metrics.snapshot().identifiers);
+}
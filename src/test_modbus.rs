@@ -0,0 +1,91 @@
+use super::*;
+
+const DEVICE_A: u64 = 0xa81758fffe000001;
+const DEVICE_B: u64 = 0xa81758fffe000002;
+
+fn map() -> RegisterMap {
+    RegisterMap::new(&[
+        Register { dev_eui: DEVICE_A, field: Field::Temperature, address: 100, scale: 10.0, encoding: Encoding::I16 },
+        Register { dev_eui: DEVICE_A, field: Field::Co2, address: 101, scale: 1.0, encoding: Encoding::I16 },
+        Register { dev_eui: DEVICE_B, field: Field::Temperature, address: 102, scale: 10.0, encoding: Encoding::I16 },
+    ]).unwrap()
+}
+
+#[test]
+fn duplicate_address() {
+    let register = Register { dev_eui: DEVICE_A, field: Field::Co2, address: 7, scale: 1.0, encoding: Encoding::I16 };
+
+    assert!(RegisterMap::new(&[register, register]).is_err());
+}
+
+#[test]
+fn update() {
+    let mut map = map();
+    assert_eq!(Some(vec![NO_VALUE; 3]), map.read(100, 3));
+
+    map.update(DEVICE_A, &Uplink { temperature: Some(-5.5), co2: Some(40000), ..Uplink::default() });
+    map.update(DEVICE_B, &Uplink { temperature: Some(21.5), ..Uplink::default() });
+    assert_eq!(Some(vec![(-55i16) as u16, NO_VALUE, 215]), map.read(100, 3));
+
+    map.update(DEVICE_A, &Uplink { co2: Some(600), ..Uplink::default() });
+    assert_eq!(Some(vec![(-55i16) as u16, 600]), map.read(100, 2));
+    assert_eq!(None, map.read(101, 3));
+    assert_eq!(None, map.read(99, 1));
+}
+
+#[test]
+fn encodings() {
+    let mut map = RegisterMap::new(&[
+        Register { dev_eui: DEVICE_A, field: Field::Light, address: 0, scale: 1.0, encoding: Encoding::U16 },
+        Register { dev_eui: DEVICE_A, field: Field::Pulse1Absolute, address: 1, scale: 1.0, encoding: Encoding::U32 },
+        Register { dev_eui: DEVICE_A, field: Field::Temperature, address: 3, scale: 10.0, encoding: Encoding::I16 },
+    ]).unwrap();
+    assert_eq!(Some(vec![u16::MAX, u16::MAX, u16::MAX, NO_VALUE]), map.read(0, 4));
+
+    map.update(DEVICE_A, &Uplink { light: Some(65000), pulse1_absolute: Some(70001), temperature: Some(-3276.8), ..Uplink::default() });
+    assert_eq!(Some(vec![65000, 1, 4465, NO_VALUE]), map.read(0, 4));
+
+    map.update(DEVICE_A, &Uplink { light: Some(u16::MAX), pulse1_absolute: Some(u32::MAX), temperature: Some(3276.7), ..Uplink::default() });
+    assert_eq!(Some(vec![u16::MAX, u16::MAX, u16::MAX, 32767]), map.read(0, 4));
+}
+
+#[test]
+fn overlapping_registers() {
+    let wide = Register { dev_eui: DEVICE_A, field: Field::Pulse1Absolute, address: 7, scale: 1.0, encoding: Encoding::U32 };
+    let narrow = Register { dev_eui: DEVICE_A, field: Field::Co2, address: 8, scale: 1.0, encoding: Encoding::I16 };
+
+    assert!(RegisterMap::new(&[wide, narrow]).is_err());
+    assert!(RegisterMap::new(&[Register { address: u16::MAX, ..wide }]).is_err());
+}
+
+#[test]
+fn handle() {
+    let mut map = map();
+    map.update(DEVICE_A, &Uplink { temperature: Some(21.5), co2: Some(600), ..Uplink::default() });
+
+    assert_eq!(Some(vec![0x12, 0x34, 0, 0, 0, 7, 1, 0x04, 4, 0x00, 0xd7, 0x02, 0x58]), map.handle(&[0x12, 0x34, 0, 0, 0, 6, 1, 0x04, 0, 100, 0, 2]));
+    assert_eq!(Some(vec![0, 1, 0, 0, 0, 5, 1, 0x03, 2, 0x00, 0xd7]), map.handle(&[0, 1, 0, 0, 0, 6, 1, 0x03, 0, 100, 0, 1]));
+    assert_eq!(Some(vec![0, 1, 0, 0, 0, 3, 1, 0x84, ILLEGAL_DATA_ADDRESS]), map.handle(&[0, 1, 0, 0, 0, 6, 1, 0x04, 0, 200, 0, 1]));
+    assert_eq!(Some(vec![0, 1, 0, 0, 0, 3, 1, 0x84, ILLEGAL_DATA_VALUE]), map.handle(&[0, 1, 0, 0, 0, 6, 1, 0x04, 0, 100, 0, 0]));
+    assert_eq!(Some(vec![0, 1, 0, 0, 0, 3, 1, 0x86, ILLEGAL_FUNCTION]), map.handle(&[0, 1, 0, 0, 0, 6, 1, 0x06, 0, 100, 0, 1]));
+    assert_eq!(None, map.handle(&[0, 1, 0, 1, 0, 6, 1, 0x04, 0, 100, 0, 1]));
+    assert_eq!(None, map.handle(&[0, 1, 0, 0, 0, 7, 1, 0x04, 0, 100, 0, 1]));
+}
+
+#[test]
+fn serves_over_tcp() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let map = Arc::new(Mutex::new(map()));
+    map.lock().unwrap().update(DEVICE_B, &Uplink { temperature: Some(21.5), ..Uplink::default() });
+    thread::spawn(move || serve(listener, map));
+
+    let mut stream = TcpStream::connect(address).unwrap();
+    stream.set_read_timeout(Some(std::time::Duration::from_secs(5))).unwrap();
+    for transaction in 0..2u8 {
+        stream.write_all(&[0, transaction, 0, 0, 0, 6, 1, 0x04, 0, 102, 0, 1]).unwrap();
+        let mut response = [0; 11];
+        stream.read_exact(&mut response).unwrap();
+        assert_eq!([0, transaction, 0, 0, 0, 5, 1, 0x04, 2, 0x00, 0xd7], response);
+    }
+}
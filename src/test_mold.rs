@@ -0,0 +1,35 @@
+use super::*;
+use crate::utilization::HOUR_S;
+
+fn reading(timestamp_s: u64, temperature: f32, humidity: u8) -> (u64, Uplink) {
+    (timestamp_s, Uplink { temperature: Some(temperature), humidity: Some(humidity), ..Uplink::default() })
+}
+
+#[test]
+fn isopleth() {
+    assert_eq!(Some(100.0), critical_humidity(0.0));
+    assert!((critical_humidity(10.0).unwrap() - 82.03).abs() < 0.01);
+    assert!((critical_humidity(20.0).unwrap() - 80.0).abs() < 0.1);
+    assert_eq!(Some(80.0), critical_humidity(30.0));
+    assert_eq!(None, critical_humidity(-1.0));
+    assert_eq!(None, critical_humidity(51.0));
+}
+
+#[test]
+fn sustained_exposure() {
+    let readings = [
+        reading(0, 20.0, 85),
+        reading(HOUR_S, 20.0, 85),
+        (HOUR_S + 60, Uplink { temperature: Some(20.0), ..Uplink::default() }),
+        reading(2 * HOUR_S, 20.0, 60),
+        reading(3 * HOUR_S, 10.0, 83),
+        reading(4 * HOUR_S, 10.0, 83),
+        reading(10 * HOUR_S, 10.0, 83),
+        reading(11 * HOUR_S, 10.0, 83),
+    ];
+
+    let risk = mold_risk(&readings, 2 * HOUR_S);
+    assert_eq!(MoldRisk { favourable_s: 6 * HOUR_S, longest_favourable_s: 3 * HOUR_S, observed_s: 7 * HOUR_S }, risk);
+    assert_eq!(Some(6.0 / 7.0), risk.ratio());
+    assert_eq!(None, mold_risk(&readings[..1], HOUR_S).ratio());
+}
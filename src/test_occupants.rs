@@ -0,0 +1,42 @@
+use super::*;
+
+fn close(expected: f32, actual: f32) -> bool {
+    (expected - actual).abs() < 0.05
+}
+
+#[test]
+fn steady_state() {
+    let room = Room::new(60.0, 2.0);
+
+    // 4 occupants: 4 * 0.018 = 2 * 60 * (C - 420) * 1e-6, so C = 1020 ppm.
+    assert!(close(4.0, room.steady_state_occupants(1020.0)));
+    assert!(close(0.0, room.steady_state_occupants(420.0)));
+    assert_eq!(0.0, room.steady_state_occupants(400.0));
+}
+
+#[test]
+fn rising() {
+    let room = Room::new(60.0, 2.0);
+
+    // At 720 ppm, ventilation removes 2 occupants' worth, and 600 ppm/h adds another 2.
+    assert!(close(4.0, room.occupants(720.0, 600.0)));
+    assert_eq!(0.0, room.occupants(720.0, -1000.0));
+}
+
+#[test]
+fn estimator() {
+    let room = Room::new(60.0, 2.0);
+    let mut estimator = Estimator::new(room, 1800);
+    let co2 = |co2| Uplink { co2: Some(co2), ..Uplink::default() };
+
+    // Four people arrive at 0 s; CO2 follows the mass balance from outdoor levels.
+    let mut estimate = None;
+    for i in 0..=12u64 {
+        let hours = i as f32 * 300.0 / 3600.0;
+        let ppm = 420.0 + 600.0 * (1.0 - (-2.0 * hours).exp());
+        estimate = estimator.push(i * 300, &co2(ppm.round() as u16));
+    }
+
+    assert!((estimate.unwrap() - 4.0).abs() < 0.5);
+    assert_eq!(None, Estimator::new(room, 1800).push(0, &co2(800)));
+}
@@ -0,0 +1,69 @@
+use super::*;
+
+const DEBOUNCE: Debounce = Debounce { occupied_after_s: 120, vacant_after_s: 600 };
+
+fn occupancy(occupancy: Occupancy) -> Uplink {
+    Uplink { occupancy: Some(occupancy), ..Uplink::default() }
+}
+
+#[test]
+fn confirms_after_dwell() {
+    let mut debouncer = Debouncer::new(DEBOUNCE);
+
+    assert_eq!(None, debouncer.push(0, &occupancy(Occupancy::OccupiedOrHeat)));
+    assert_eq!(None, debouncer.push(60, &occupancy(Occupancy::OccupiedOrHeat)));
+    assert_eq!(
+        Some(Change { presence: Presence::Occupied, since_s: 0, confirmed_s: 120 }),
+        debouncer.push(120, &occupancy(Occupancy::OccupiedOrHeat))
+    );
+    assert_eq!(Some(Presence::Occupied), debouncer.presence());
+}
+
+#[test]
+fn pending_does_not_flap() {
+    let mut debouncer = Debouncer::new(DEBOUNCE);
+    debouncer.push(0, &occupancy(Occupancy::OccupiedOrHeat));
+    debouncer.push(120, &occupancy(Occupancy::OccupiedOrHeat));
+
+    for i in 0..10 {
+        let state = if i % 2 == 0 { Occupancy::PendingOrPir } else { Occupancy::OccupiedOrHeat };
+        assert_eq!(None, debouncer.push(200 + i * 60, &occupancy(state)));
+    }
+    assert_eq!(Some(Presence::Occupied), debouncer.presence());
+}
+
+#[test]
+fn short_vacancy_is_suppressed() {
+    let mut debouncer = Debouncer::new(DEBOUNCE);
+    debouncer.push(0, &occupancy(Occupancy::OccupiedOrHeat));
+    debouncer.push(120, &occupancy(Occupancy::OccupiedOrHeat));
+
+    assert_eq!(None, debouncer.push(200, &occupancy(Occupancy::NoBody)));
+    assert_eq!(None, debouncer.push(500, &occupancy(Occupancy::OccupiedOrHeat)));
+    assert_eq!(None, debouncer.push(600, &occupancy(Occupancy::NoBody)));
+    assert_eq!(None, debouncer.push(900, &occupancy(Occupancy::PendingOrPir)));
+    assert_eq!(
+        Some(Change { presence: Presence::Vacant, since_s: 600, confirmed_s: 1200 }),
+        debouncer.push(1200, &occupancy(Occupancy::PendingOrPir))
+    );
+}
+
+#[test]
+fn transitions() {
+    let readings = [
+        (0, occupancy(Occupancy::NoBody)),
+        (600, occupancy(Occupancy::NoBody)),
+        (900, occupancy(Occupancy::OccupiedOrHeat)),
+        (1020, occupancy(Occupancy::OccupiedOrHeat)),
+        (3000, occupancy(Occupancy::NoBody)),
+        (3300, occupancy(Occupancy::PendingOrPir)),
+        (3600, occupancy(Occupancy::NoBody)),
+        (4000, occupancy(Occupancy::OccupiedOrHeat)),
+        (4200, occupancy(Occupancy::OccupiedOrHeat)),
+    ];
+
+    assert_eq!(
+        vec![Event::Entered { at_s: 900 }, Event::Vacated { at_s: 3000, occupied_s: 2100 }, Event::Entered { at_s: 4000 }],
+        events(&readings, DEBOUNCE)
+    );
+}
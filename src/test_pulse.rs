@@ -0,0 +1,48 @@
+use super::*;
+
+fn absolute(timestamp_s: u64, count: u32) -> (u64, Uplink) {
+    (timestamp_s, Uplink { pulse1_absolute: Some(count), ..Uplink::default() })
+}
+
+#[test]
+fn absolute_counts() {
+    let readings = [absolute(0, 100), absolute(600, 150), (900, Uplink::default()), absolute(1800, 150)];
+
+    assert_eq!(
+        vec![Rate { start_s: 0, end_s: 600, pulses: 50 }, Rate { start_s: 600, end_s: 1800, pulses: 0 }],
+        absolute_rates(&readings)
+    );
+    assert_eq!(300.0, absolute_rates(&readings)[0].per_hour());
+}
+
+#[test]
+fn rollover_and_reset() {
+    let readings = [absolute(0, u32::MAX - 9), absolute(600, 20), absolute(1200, 5)];
+
+    let pulses = absolute_rates(&readings).iter().map(|rate| rate.pulses).collect::<Vec<_>>();
+    assert_eq!(vec![30, 5], pulses);
+}
+
+#[test]
+fn relative() {
+    let readings = [
+        (0, Uplink { pulse1: Some(7), ..Uplink::default() }),
+        (600, Uplink { pulse1: Some(20), ..Uplink::default() }),
+        (600, Uplink { pulse1: Some(3), ..Uplink::default() }),
+        (1200, Uplink { pulse1: Some(10), ..Uplink::default() }),
+    ];
+
+    assert_eq!(
+        vec![Rate { start_s: 0, end_s: 600, pulses: 20 }, Rate { start_s: 600, end_s: 1200, pulses: 10 }],
+        relative_rates(&readings)
+    );
+    assert!(relative_rates(&readings[..1]).is_empty());
+}
+
+#[test]
+fn flow() {
+    let rate = Rate { start_s: 0, end_s: 300, pulses: 50 };
+
+    assert_eq!(600.0, rate.per_hour());
+    assert_eq!(1.0, rate.flow_per_minute(0.1));
+}
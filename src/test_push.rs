@@ -0,0 +1,74 @@
+use super::*;
+
+const INPUT: [u8; 14] = [0x01, 0x00, 0xdc, 0x02, 0x36, 0x07, 0x0e, 0x41, 0x06, 0x02, 0x58, 0x0d, 0x01, 0x11];
+
+#[test]
+fn any_chunking() {
+    let input = [&INPUT[..], &[0x02]].concat();
+    let expected = Uplink::deserialize(&input).unwrap();
+
+    for chunk_size in 1..=input.len() {
+        let mut parser = PushParser::new();
+        let mut completed = 0;
+        for chunk in input.chunks(chunk_size) {
+            completed += parser.feed(chunk).unwrap();
+        }
+        assert_eq!(6, completed);
+        let uplink = parser.finish().unwrap();
+        assert_eq!(expected, uplink);
+        assert_eq!(Some(0x36), uplink.humidity_percent());
+        assert!(uplink.undecoded_identifiers().is_empty());
+    }
+}
+
+#[test]
+fn fields_as_they_complete() {
+    let mut parser = PushParser::new();
+
+    assert_eq!(0, parser.feed(&INPUT[..2]).unwrap());
+    assert_eq!(None, parser.uplink().temperature());
+    assert_eq!(1, parser.feed(&INPUT[2..3]).unwrap());
+    assert_eq!(Some(22.0), parser.uplink().temperature());
+}
+
+#[test]
+fn next_payload() {
+    let mut parser = PushParser::new();
+
+    parser.feed(&[0x01, 0x00, 0xdc]).unwrap();
+    assert_eq!(Some(22.0), parser.finish().unwrap().temperature());
+    parser.feed(&[0x06, 0x02, 0x58]).unwrap();
+    let uplink = parser.finish().unwrap();
+    assert_eq!(None, uplink.temperature());
+    assert_eq!(Some(600), uplink.co2_ppm());
+}
+
+#[test]
+fn errors() {
+    let mut parser = PushParser::new();
+    assert_eq!(1, parser.feed(&[0x11, 0x02]).unwrap());
+    let error = parser.feed(&[0x20, 0x01, 0x00, 0xdc]).unwrap_err();
+    assert_eq!(0, parser.feed(&[0x01, 0x00, 0xdc]).unwrap());
+    let finished = parser.finish().unwrap_err();
+    assert_eq!(error.kind(), finished.kind());
+    assert_eq!(error.to_string(), finished.to_string());
+    assert!(finished.to_string().contains("index 2"));
+
+    assert!(parser.feed(&[0x11, 0x03]).is_err());
+    assert!(parser.finish().is_err());
+
+    parser.feed(&[0x01, 0x00]).unwrap();
+    assert!(parser.finish().is_err());
+    assert_eq!(Uplink::default(), parser.finish().unwrap());
+}
+
+#[test]
+fn error_detail() {
+    let mut parser = PushParser::with_error_detail(ErrorDetail::Redacted);
+    let error = parser.feed(&[0x11, 0x03]).unwrap_err().to_string();
+    assert_eq!("2-byte payload: index 1 is not an occupancy value", error);
+    assert_eq!(error, parser.finish().unwrap_err().to_string());
+
+    let error = parser.feed(&[0x11, 0x03]).unwrap_err().to_string();
+    assert_eq!("2-byte payload: index 1 is not an occupancy value", error);
+}
@@ -0,0 +1,46 @@
+use super::*;
+
+const GATEWAY_A: u64 = 0x0000024b0b030001;
+const GATEWAY_B: u64 = 0x0000024b0b030002;
+const GATEWAY_C: u64 = 0x0000024b0b030003;
+
+#[test]
+fn best_gateway() {
+    let radio = RadioMeta { receptions: vec![
+        Reception { gateway_id: GATEWAY_A, rssi_dbm: -110, snr_db: 2.5 },
+        Reception { gateway_id: GATEWAY_B, rssi_dbm: -95, snr_db: 7.0 },
+        Reception { gateway_id: GATEWAY_C, rssi_dbm: -90, snr_db: 7.0 },
+    ] };
+
+    assert_eq!(Some(GATEWAY_C), radio.best_gateway());
+    assert_eq!(None, RadioMeta::default().best_gateway());
+}
+
+#[test]
+fn merge() {
+    let mut radio = RadioMeta { receptions: vec![Reception { gateway_id: GATEWAY_A, rssi_dbm: -110, snr_db: 2.5 }] };
+    radio.merge(&RadioMeta { receptions: vec![
+        Reception { gateway_id: GATEWAY_A, rssi_dbm: -100, snr_db: 4.0 },
+        Reception { gateway_id: GATEWAY_B, rssi_dbm: -120, snr_db: -5.0 },
+    ] });
+
+    assert_eq!(vec![
+        Reception { gateway_id: GATEWAY_A, rssi_dbm: -100, snr_db: 4.0 },
+        Reception { gateway_id: GATEWAY_B, rssi_dbm: -120, snr_db: -5.0 },
+    ], radio.receptions);
+    assert_eq!(Some(GATEWAY_A), radio.best_gateway());
+}
+
+#[test]
+fn merge_agrees_with_best() {
+    let existing = Reception { gateway_id: GATEWAY_A, rssi_dbm: -100, snr_db: 4.0 };
+    let nan = Reception { gateway_id: GATEWAY_A, rssi_dbm: -110, snr_db: f32::NAN };
+
+    let mut radio = RadioMeta { receptions: vec![existing] };
+    radio.merge(&RadioMeta { receptions: vec![nan] });
+    let best = *RadioMeta { receptions: vec![existing, nan] }.best().unwrap();
+
+    assert_eq!(1, radio.receptions.len());
+    assert_eq!(best.rssi_dbm, radio.receptions[0].rssi_dbm);
+    assert!(radio.receptions[0].snr_db.is_nan());
+}
@@ -0,0 +1,48 @@
+use super::*;
+
+fn record() -> Record {
+    Record { timestamp_s: 1_700_000_000, dev_eui: 0xa81758fffe000001, payload: vec![0x01, 0x00, 0xdc, 0x07, 0x0e, 0x41] }
+}
+
+#[test]
+fn round_trip() {
+    let bytes = record().to_bytes().unwrap();
+
+    assert_eq!(record().encoded_len(), bytes.len());
+    assert_eq!(&[0x00, 0x06], &bytes[16..18]);
+    assert_eq!(record(), Record::from_bytes(&bytes).unwrap());
+}
+
+#[test]
+fn read_sequence() {
+    let mut bytes = Vec::new();
+    record().write_to(&mut bytes).unwrap();
+    Record { payload: Vec::new(), ..record() }.write_to(&mut bytes).unwrap();
+
+    let mut reader = bytes.as_slice();
+    assert_eq!(Some(record()), Record::read_from(&mut reader).unwrap());
+    assert_eq!(Some(Record { payload: Vec::new(), ..record() }), Record::read_from(&mut reader).unwrap());
+    assert_eq!(None, Record::read_from(&mut reader).unwrap());
+}
+
+#[test]
+fn truncated() {
+    let bytes = record().to_bytes().unwrap();
+
+    assert!(Record::from_bytes(&bytes[..10]).is_err());
+    assert!(Record::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    assert!(Record::from_bytes(&[bytes.as_slice(), &[0]].concat()).is_err());
+    for length in 1..bytes.len() {
+        assert!(Record::read_from(&mut &bytes[..length]).is_err());
+    }
+}
+
+#[test]
+fn payload_too_long() {
+    assert!(Record { payload: vec![0; 0x10000], ..record() }.to_bytes().is_err());
+}
+
+#[test]
+fn uplink() {
+    assert_eq!(Some(22.0), record().uplink().unwrap().temperature());
+}
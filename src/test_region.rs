@@ -0,0 +1,44 @@
+use super::*;
+
+#[test]
+fn data_rates() {
+    assert_eq!(Some(Modulation::lorawan(12, 125_000)), Region::Eu868.modulation(0));
+    assert_eq!(Some(Modulation::lorawan(7, 250_000)), Region::As923.modulation(6));
+    assert_eq!(Some(Modulation::lorawan(8, 500_000)), Region::Us915.modulation(4));
+    assert_eq!(None, Region::Us915.modulation(5));
+
+    assert_eq!(Some(51), Region::Eu868.max_payload(0));
+    assert_eq!(Some(11), Region::Us915.max_payload(0));
+    assert_eq!(None, Region::Eu868.max_payload(7));
+}
+
+#[test]
+fn as923_data_rates() {
+    let max_payloads = (0..7).map(|data_rate| Region::As923.max_payload(data_rate)).collect::<Vec<_>>();
+    assert_eq!(vec![Some(51), Some(51), Some(115), Some(115), Some(242), Some(242), Some(242)], max_payloads);
+    assert_eq!(Some(Modulation::lorawan(10, 125_000)), Region::As923.modulation(2));
+
+    assert!(Region::As923.validate_uplink(2, 115).is_ok());
+    assert!(Region::Eu868.validate_uplink(2, 115).is_err());
+}
+
+#[test]
+fn duty_cycle() {
+    let mut eu868 = Region::Eu868.duty_cycle();
+    assert_eq!(Some(36_000_000), eu868.remaining_us(0, 868_100_000));
+    assert_eq!(Some(360_000_000), eu868.remaining_us(0, 869_525_000));
+
+    let mut us915 = Region::Us915.duty_cycle();
+    assert_eq!(None, us915.remaining_us(0, 902_300_000));
+}
+
+#[test]
+fn validate_uplink() {
+    assert!(Region::Eu868.validate_uplink(0, 51).is_ok());
+    assert!(Region::Eu868.validate_uplink(0, 52).is_err());
+    assert!(Region::Eu868.validate_uplink(9, 1).is_err());
+
+    assert!(Region::Us915.validate_uplink(0, 11).is_ok());
+    assert!(Region::Us915.validate_uplink(3, 242).is_ok());
+    assert_eq!(ErrorKind::InvalidInput, Region::Us915.validate_uplink(0, 12).unwrap_err().kind());
+}
@@ -0,0 +1,52 @@
+use super::*;
+
+fn record(timestamp_s: u64) -> Record {
+    Record { timestamp_s, dev_eui: 0xa81758fffe000001, payload: vec![0x01, 0x00, 0xdc] }
+}
+
+#[test]
+fn delays() {
+    let records = vec![Ok(record(1000)), Ok(record(1600)), Ok(record(1500)), Ok(record(1900))];
+
+    let delays = Replay::with_speed(records.into_iter(), 60.0).unwrap().map(|item| item.unwrap().0).collect::<Vec<_>>();
+    assert_eq!(vec![Duration::ZERO, Duration::from_secs(10), Duration::ZERO, Duration::from_secs(5)], delays);
+}
+
+#[test]
+fn run() {
+    let records = vec![Ok(record(1000)), Ok(record(1001)), Ok(record(1002))];
+    let start = std::time::Instant::now();
+
+    let mut temperatures = Vec::new();
+    Replay::with_speed(records.into_iter(), 20.0).unwrap().run(|record| {
+        temperatures.push(record.uplink()?.temperature());
+        Ok(())
+    }).unwrap();
+
+    assert_eq!(vec![Some(22.0); 3], temperatures);
+    assert!(start.elapsed() >= Duration::from_millis(100));
+}
+
+#[test]
+fn stops_at_error() {
+    let records = vec![Ok(record(1000)), Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof)), Ok(record(1001))];
+
+    let mut count = 0;
+    assert!(Replay::with_speed(records.into_iter(), 1000.0).unwrap().run(|_| { count += 1; Ok(()) }).is_err());
+    assert_eq!(1, count);
+}
+
+#[test]
+fn invalid_speeds() {
+    for speed in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+        assert_eq!(std::io::ErrorKind::InvalidInput, Replay::with_speed(Vec::new().into_iter(), speed).err().unwrap().kind());
+    }
+}
+
+#[test]
+fn overlong_delay() {
+    let records = vec![Ok(record(0)), Ok(record(u64::MAX))];
+
+    let delays = Replay::with_speed(records.into_iter(), f64::MIN_POSITIVE).unwrap().map(|item| item.unwrap().0).collect::<Vec<_>>();
+    assert_eq!(vec![Duration::ZERO, Duration::MAX], delays);
+}
@@ -0,0 +1,102 @@
+use super::*;
+
+const GRID: Grid = Grid { start_s: 0, end_s: 400, step_s: 100, max_gap_s: 250 };
+
+#[test]
+fn resample_linear() {
+    let readings = [
+        (50, Uplink { temperature: Some(20.0), co2: Some(400), ..Uplink::default() }),
+        (250, Uplink { temperature: Some(22.0), co2: Some(600), ..Uplink::default() }),
+    ];
+
+    assert_eq!(
+        vec![
+            (0, Uplink::default()),
+            (100, Uplink { temperature: Some(20.5), co2: Some(450), ..Uplink::default() }),
+            (200, Uplink { temperature: Some(21.5), co2: Some(550), ..Uplink::default() }),
+            (300, Uplink::default()),
+        ],
+        resample(&readings, &GRID, Fill::Linear)
+    );
+}
+
+#[test]
+fn resample_previous() {
+    let readings = [
+        (250, Uplink { temperature: Some(22.0), ..Uplink::default() }),
+        (50, Uplink { temperature: Some(20.0), ..Uplink::default() }),
+    ];
+
+    assert_eq!(
+        vec![
+            (0, Uplink::default()),
+            (100, Uplink { temperature: Some(20.0), ..Uplink::default() }),
+            (200, Uplink { temperature: Some(20.0), ..Uplink::default() }),
+            (300, Uplink { temperature: Some(22.0), ..Uplink::default() }),
+        ],
+        resample(&readings, &GRID, Fill::Previous)
+    );
+}
+
+#[test]
+fn resample_never_interpolates_states() {
+    let readings = [
+        (0, Uplink { occupancy: Some(Occupancy::NoBody), external_digital: Some(false), ..Uplink::default() }),
+        (200, Uplink { occupancy: Some(Occupancy::OccupiedOrHeat), external_digital: Some(true), ..Uplink::default() }),
+    ];
+
+    let resampled = resample(&readings, &GRID, Fill::Linear);
+    assert_eq!(Some(Occupancy::NoBody), resampled[1].1.occupancy());
+    assert_eq!(Some(false), resampled[1].1.external_digital());
+    assert_eq!(Some(Occupancy::OccupiedOrHeat), resampled[3].1.occupancy());
+}
+
+#[test]
+fn resample_counts() {
+    let readings = [
+        (40, Uplink { motion: Some(2), pulse1: Some(10), ..Uplink::default() }),
+        (90, Uplink { motion: Some(3), pulse1: Some(20), ..Uplink::default() }),
+        (250, Uplink { motion: Some(4), pulse1: Some(30), ..Uplink::default() }),
+        (500, Uplink { motion: Some(9), pulse1: Some(40), ..Uplink::default() }),
+    ];
+
+    let resampled = resample(&readings, &GRID, Fill::Linear);
+    let motion = resampled.iter().map(|(_, uplink)| uplink.motion).collect::<Vec<_>>();
+    assert_eq!(vec![Some(5), None, Some(4), None], motion);
+    let pulse1 = resampled.iter().map(|(_, uplink)| uplink.pulse1).collect::<Vec<_>>();
+    assert_eq!(vec![Some(30), None, Some(30), None], pulse1);
+
+    let readings = [(150, Uplink { acceleration_motion: Some(1), ..Uplink::default() })];
+    let resampled = resample(&readings, &GRID, Fill::Previous);
+    assert_eq!(Some(1), resampled[1].1.acceleration_motion);
+    assert_eq!(None, resampled[2].1.acceleration_motion);
+}
+
+#[test]
+fn resample_absolute_pulse_count() {
+    let readings = [
+        (100, Uplink { pulse1_absolute: Some(16_777_217), ..Uplink::default() }),
+        (250, Uplink { pulse1_absolute: Some(16_777_317), ..Uplink::default() }),
+    ];
+
+    let pulse1_absolute = resample(&readings, &GRID, Fill::Linear).iter().map(|(_, uplink)| uplink.pulse1_absolute).collect::<Vec<_>>();
+    assert_eq!(vec![None, Some(16_777_217), Some(16_777_217), Some(16_777_317)], pulse1_absolute);
+}
+
+#[test]
+fn resample_respects_max_gap() {
+    let readings = [
+        (0, Uplink { battery_mv: Some(3600), ..Uplink::default() }),
+        (300, Uplink { battery_mv: Some(3500), ..Uplink::default() }),
+    ];
+
+    assert_eq!(Some(3600), resample(&readings, &GRID, Fill::Previous)[2].1.battery_mv);
+    assert_eq!(None, resample(&readings, &GRID, Fill::Linear)[1].1.battery_mv);
+}
+
+#[test]
+fn resample_zero_step() {
+    let readings = [(0, Uplink { temperature: Some(20.0), ..Uplink::default() })];
+
+    assert!(resample(&readings, &Grid { step_s: 0, ..GRID }, Fill::Linear).is_empty());
+}
@@ -0,0 +1,176 @@
+use super::*;
+use crate::condensation::Derived;
+
+fn co2_high() -> Rule {
+    Rule {
+        name: String::from("co2 high"),
+        field: Field::Co2.into(),
+        operator: Operator::Above,
+        threshold: 1000.0,
+        hysteresis: 100.0,
+        dwell_s: 600,
+        severity: Severity::Warning,
+    }
+}
+
+fn co2(ppm: u16) -> Uplink {
+    Uplink { co2: Some(ppm), ..Uplink::default() }
+}
+
+#[test]
+fn raises_after_dwell_and_clears_with_hysteresis() {
+    let mut engine = Engine::new();
+    engine.add(co2_high());
+
+    assert!(engine.push(0, &co2(1100)).is_empty());
+    assert!(engine.push(300, &co2(1200)).is_empty());
+    assert_eq!(
+        vec![Event {
+            rule: String::from("co2 high"),
+            severity: Severity::Warning,
+            transition: Transition::Raised,
+            timestamp_s: 600,
+            value: 1150.0,
+        }],
+        engine.push(600, &co2(1150))
+    );
+    assert_eq!(1, engine.raised().count());
+
+    assert!(engine.push(900, &co2(950)).is_empty());
+    let events = engine.push(1200, &co2(850));
+    assert_eq!(1, events.len());
+    assert_eq!(Transition::Cleared, events[0].transition);
+    assert_eq!(0, engine.raised().count());
+}
+
+#[test]
+fn dwell_restarts_when_condition_lapses() {
+    let mut engine = Engine::new();
+    engine.add(co2_high());
+
+    assert!(engine.push(0, &co2(1100)).is_empty());
+    assert!(engine.push(300, &co2(900)).is_empty());
+    assert!(engine.push(600, &co2(1100)).is_empty());
+    assert!(engine.push(900, &co2(1100)).is_empty());
+    assert_eq!(Transition::Raised, engine.push(1200, &co2(1100))[0].transition);
+}
+
+#[test]
+fn below_operator() {
+    let mut engine = Engine::new();
+    engine.add(Rule {
+        name: String::from("battery low"),
+        field: Field::Battery.into(),
+        operator: Operator::Below,
+        threshold: 3.0,
+        hysteresis: 0.2,
+        dwell_s: 0,
+        severity: Severity::Critical,
+    });
+    let battery = |mv| Uplink { battery_mv: Some(mv), ..Uplink::default() };
+
+    assert_eq!(Transition::Raised, engine.push(0, &battery(2900))[0].transition);
+    assert!(engine.push(10, &battery(3100)).is_empty());
+    assert_eq!(Transition::Cleared, engine.push(20, &battery(3300))[0].transition);
+}
+
+#[test]
+fn fields_absent_from_uplink_are_ignored() {
+    let mut engine = Engine::new();
+    engine.add(Rule { dwell_s: 0, ..co2_high() });
+
+    assert!(engine.push(0, &Uplink { temperature: Some(21.0), ..Uplink::default() }).is_empty());
+    assert_eq!(1, engine.push(10, &co2(1100)).len());
+}
+
+#[test]
+fn condensation_and_frost() {
+    let mut engine = Engine::new();
+    engine.add(Rule {
+        name: String::from("condensation"),
+        field: Derived::DewPointMargin.into(),
+        operator: Operator::Below,
+        threshold: 2.0,
+        hysteresis: 1.0,
+        dwell_s: 0,
+        severity: Severity::Warning,
+    });
+    engine.add(Rule {
+        name: String::from("frost"),
+        field: Field::ExternalTemperature1.into(),
+        operator: Operator::Below,
+        threshold: 0.0,
+        hysteresis: 0.5,
+        dwell_s: 0,
+        severity: Severity::Critical,
+    });
+    let surface = |surface| Uplink {
+        temperature: Some(20.0),
+        humidity: Some(50),
+        external_temperature_1: Some(surface),
+        ..Uplink::default()
+    };
+
+    // The dew point is about 9.3 °C.
+    assert!(engine.push(0, &surface(15.0)).is_empty());
+    let events = engine.push(10, &surface(10.0));
+    assert_eq!(vec!["condensation"], events.iter().map(|e| e.rule.as_str()).collect::<Vec<_>>());
+    assert!((events[0].value - 0.74).abs() < 0.05);
+    let events = engine.push(20, &surface(-1.0));
+    assert_eq!(vec!["frost"], events.iter().map(|e| e.rule.as_str()).collect::<Vec<_>>());
+    assert_eq!(2, engine.push(30, &surface(14.0)).len());
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn from_toml() {
+    let engine = Engine::from_toml(
+        r#"
+        [[rule]]
+        name = "co2 high"
+        field = "co2"
+        operator = "above"
+        threshold = 1000.0
+        hysteresis = 100.0
+        dwell_s = 600
+        severity = "warning"
+
+        [[rule]]
+        name = "battery low"
+        field = "battery"
+        operator = "below"
+        threshold = 3.0
+        severity = "critical"
+        "#,
+    )
+    .unwrap();
+
+    let rules = engine.rules().collect::<Vec<_>>();
+    assert_eq!(&co2_high(), rules[0]);
+    assert_eq!(0, rules[1].dwell_s);
+    assert_eq!(Severity::Critical, rules[1].severity);
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn from_toml_derived() {
+    let engine = Engine::from_toml(
+        r#"
+        [[rule]]
+        name = "condensation"
+        field = "dew_point_margin"
+        operator = "below"
+        threshold = 2.0
+        severity = "warning"
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(Quantity::Derived(Derived::DewPointMargin), engine.rules().next().unwrap().field);
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn from_toml_invalid() {
+    assert!(Engine::from_toml("[[rule]]\nname = \"x\"\nfield = \"humidity\"").is_err());
+}
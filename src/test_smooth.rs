@@ -0,0 +1,40 @@
+use super::*;
+
+fn co2(co2: u16) -> Uplink {
+    Uplink { co2: Some(co2), ..Uplink::default() }
+}
+
+fn values(smoother: &mut Smoother, readings: &[u16]) -> Vec<(f32, bool)> {
+    readings.iter().map(|r| smoother.push(&co2(*r)).map(|s| (s.value, s.outlier)).unwrap()).collect()
+}
+
+#[test]
+fn median() {
+    let mut smoother = Smoother::new(Field::Co2, Smoothing { window: 3, max_deviations: None });
+
+    assert_eq!(None, smoother.push(&Uplink::default()));
+    assert_eq!(
+        vec![(500.0, false), (2000.0, false), (510.0, false), (520.0, false), (520.0, false)],
+        values(&mut smoother, &[500, 2000, 510, 520, 900]),
+    );
+}
+
+#[test]
+fn outliers() {
+    let mut smoother = Smoother::new(Field::Co2, Smoothing { window: 5, max_deviations: Some(5.0) });
+
+    assert_eq!(
+        vec![(500.0, false), (510.0, false), (500.0, false), (505.0, false), (505.0, true), (505.0, false)],
+        values(&mut smoother, &[500, 510, 495, 505, 5000, 520]),
+    );
+}
+
+#[test]
+fn step_change() {
+    let mut smoother = Smoother::new(Field::Co2, Smoothing { window: 4, max_deviations: Some(5.0) });
+
+    assert_eq!(
+        vec![(500.0, false), (510.0, false), (505.0, false), (505.0, true), (800.0, true), (800.0, false)],
+        values(&mut smoother, &[500, 510, 505, 800, 800, 800]),
+    );
+}
@@ -0,0 +1,34 @@
+use super::*;
+
+#[test]
+fn full() {
+    let uplink = Uplink::deserialize(&[0x01, 0x00, 0xd5, 0x02, 0x2a, 0x06, 0x02, 0x58, 0x07, 0x0e, 0x10, 0x11, 0x02, 0x0d, 0x01, 0x04, 0x00, 0x10]).unwrap();
+
+    assert_eq!(
+        "temperature: 21.3\nco2_ppm: 600\nbattery_voltage: 3.600\noccupancy: occupied_or_heat\nexternal_digital: true\nhumidity_percent: 42\nlight_lux: 16\n",
+        render(&uplink)
+    );
+}
+
+#[test]
+fn absent_fields() {
+    assert_eq!("", render(&Uplink::default()));
+    assert_eq!("temperature: -0.1\n", render(&Uplink { temperature: Some(-0.1), ..Uplink::default() }));
+}
+
+#[test]
+fn acceleration() {
+    assert_eq!("acceleration_raw: 1 -63 0\n", render(&Uplink { acceleration: Some([1, -63, 0]), ..Uplink::default() }));
+}
+
+#[test]
+fn gps() {
+    assert_eq!("gps: -34.6037 -58.3816\n", render(&Uplink { gps: Some([-346037, -583816]), ..Uplink::default() }));
+}
+
+#[test]
+fn readings() {
+    let readings = [(60, Uplink { co2: Some(500), ..Uplink::default() }), (0, Uplink::default())];
+
+    assert_eq!("timestamp_s: 60\nco2_ppm: 500\n\ntimestamp_s: 0\n", render_readings(&readings));
+}
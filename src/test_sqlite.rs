@@ -0,0 +1,59 @@
+use super::*;
+
+const DEV_EUI: u64 = 0xa81758fffe000001;
+
+#[test]
+fn insert_and_range() {
+    let store = Store::open_in_memory().unwrap();
+    let uplink0 = Uplink { temperature: Some(21.5), battery_mv: Some(3600), occupancy: Some(Occupancy::PendingOrPir), ..Uplink::default() };
+    let uplink1 = Uplink { co2: Some(812), external_digital: Some(true), humidity: Some(45), light: Some(320), motion: Some(3), acceleration: Some([1, -63, 0]), gps: Some([-346037, -583816]), pulse1: Some(12), pulse1_absolute: Some(u32::MAX), external_temperature_1: Some(-5.5), distance: Some(1250), acceleration_motion: Some(7), waterleak: Some(255), ..Uplink::default() };
+
+    store.insert(DEV_EUI, 200, &uplink1).unwrap();
+    store.insert(DEV_EUI, 100, &uplink0).unwrap();
+    store.insert(DEV_EUI, 300, &uplink0).unwrap();
+    store.insert(1, 150, &uplink0).unwrap();
+
+    assert_eq!(vec![(100, uplink0), (200, uplink1)], store.range(DEV_EUI, 100, 300).unwrap());
+    assert!(store.range(DEV_EUI, 400, 500).unwrap().is_empty());
+}
+
+#[test]
+fn prune() {
+    let store = Store::open_in_memory().unwrap();
+    for timestamp_s in [100, 200, 300] {
+        store.insert(DEV_EUI, timestamp_s, &Uplink::default()).unwrap();
+    }
+
+    assert_eq!(2, store.prune(300).unwrap());
+    assert_eq!(1, store.range(DEV_EUI, 0, u64::MAX >> 1).unwrap().len());
+}
+
+#[test]
+fn open_file() {
+    let path = std::env::temp_dir().join(format!("elsys_test_sqlite_{}.db", std::process::id()));
+    {
+        let store = Store::open(&path).unwrap();
+        store.insert(DEV_EUI, 100, &Uplink { co2: Some(600), ..Uplink::default() }).unwrap();
+    }
+
+    let store = Store::open(&path).unwrap();
+    assert_eq!(Some(600), store.range(DEV_EUI, 0, 1000).unwrap()[0].1.co2_ppm());
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn adds_columns_to_existing_tables() {
+    let connection = Connection::open_in_memory().unwrap();
+    connection.execute_batch(
+        "CREATE TABLE uplink (dev_eui INTEGER NOT NULL, timestamp_s INTEGER NOT NULL, temperature REAL, co2_ppm INTEGER, battery_mv INTEGER, occupancy INTEGER, external_digital INTEGER);
+        INSERT INTO uplink (dev_eui, timestamp_s, co2_ppm) VALUES (1, 100, 600);",
+    ).unwrap();
+
+    let store = Store::new(connection).unwrap();
+    store.insert(1, 200, &Uplink { humidity: Some(45), ..Uplink::default() }).unwrap();
+
+    assert_eq!(
+        vec![(100, Uplink { co2: Some(600), ..Uplink::default() }), (200, Uplink { humidity: Some(45), ..Uplink::default() })],
+        store.range(1, 0, 1000).unwrap()
+    );
+}
@@ -0,0 +1,56 @@
+use super::*;
+
+const DEV_EUI: u64 = 0xa81758fffe000001;
+
+#[test]
+fn metric_deltas() {
+    let previous = Snapshot { successes: 5, failures: 1, identifiers: vec![(0x01, 5)], latency: vec![6, 0] };
+    let current = Snapshot { successes: 7, failures: 1, identifiers: vec![(0x01, 7), (0x11, 2)], latency: vec![7, 1] };
+
+    assert_eq!(
+        vec![
+            "elsys.decode.success:2|c",
+            "elsys.identifier.0x01:2|c",
+            "elsys.identifier.0x11:2|c",
+            "elsys.latency.le_1000ns:1|c",
+            "elsys.latency.le_2000ns:1|c",
+        ],
+        metric_lines("elsys", &previous, &current)
+    );
+}
+
+#[test]
+fn uplink_gauges() {
+    let uplink = Uplink { temperature: Some(21.5), co2: Some(640), ..Uplink::default() };
+    let fields = [Field::Temperature, Field::Co2, Field::Battery];
+
+    assert_eq!(
+        vec!["elsys.a81758fffe000001.temperature:21.5|g", "elsys.a81758fffe000001.co2_ppm:640|g"],
+        uplink_lines("elsys", Flavour::StatsD, DEV_EUI, &uplink, &fields)
+    );
+    assert_eq!(
+        vec!["elsys.temperature:21.5|g|#dev_eui:a81758fffe000001", "elsys.co2_ppm:640|g|#dev_eui:a81758fffe000001"],
+        uplink_lines("elsys", Flavour::DogStatsD, DEV_EUI, &uplink, &fields)
+    );
+}
+
+#[test]
+fn sends_over_udp() {
+    let receiver = UdpSocket::bind("127.0.0.1:0").unwrap();
+    receiver.set_read_timeout(Some(std::time::Duration::from_secs(5))).unwrap();
+    let mut emitter = Emitter::new(receiver.local_addr().unwrap(), "elsys", Flavour::StatsD).unwrap();
+    let mut buffer = [0; 1500];
+
+    emitter.send_uplink(DEV_EUI, &Uplink { co2: Some(640), ..Uplink::default() }, Field::ALL).unwrap();
+    let length = receiver.recv(&mut buffer).unwrap();
+    assert_eq!(b"elsys.a81758fffe000001.co2_ppm:640|g", &buffer[..length]);
+
+    let snapshot = Snapshot { successes: 3, ..Snapshot::default() };
+    emitter.send_metrics(&snapshot).unwrap();
+    let length = receiver.recv(&mut buffer).unwrap();
+    assert_eq!(b"elsys.decode.success:3|c", &buffer[..length]);
+
+    emitter.send_metrics(&Snapshot { successes: 4, ..Snapshot::default() }).unwrap();
+    let length = receiver.recv(&mut buffer).unwrap();
+    assert_eq!(b"elsys.decode.success:1|c", &buffer[..length]);
+}
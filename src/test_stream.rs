@@ -0,0 +1,56 @@
+use super::*;
+use crate::Occupancy;
+
+/// Hands out at most one byte per read.
+struct Trickle<'a>(&'a [u8]);
+
+impl Read for Trickle<'_> {
+    fn read(&mut self, buffer: &mut [u8]) -> Result<usize> {
+        match (self.0.split_first(), buffer.first_mut()) {
+            (Some((byte, rest)), Some(slot)) => {
+                *slot = *byte;
+                self.0 = rest;
+                Ok(1)
+            }
+            _ => Ok(0),
+        }
+    }
+}
+
+#[test]
+fn length_prefixed() {
+    let input = [0x00, 0x03, 0x01, 0x00, 0xdc, 0x00, 0x03, 0x06, 0x02, 0x58];
+
+    let uplinks = UplinkStream::new(Trickle(&input), Framing::LengthPrefixed).collect::<Result<Vec<_>>>().unwrap();
+    assert_eq!(vec![Some(22.0), None], uplinks.iter().map(Uplink::temperature).collect::<Vec<_>>());
+    assert_eq!(vec![None, Some(600)], uplinks.iter().map(Uplink::co2_ppm).collect::<Vec<_>>());
+}
+
+#[test]
+fn length_prefixed_truncated() {
+    let input = [0x00, 0x03, 0x01, 0x00, 0xdc, 0x00, 0x03, 0x06];
+
+    let mut stream = UplinkStream::new(&input[..], Framing::LengthPrefixed);
+    assert!(stream.next().unwrap().is_ok());
+    assert_eq!(ErrorKind::UnexpectedEof, stream.next().unwrap().unwrap_err().kind());
+    assert!(stream.next().is_none());
+}
+
+#[test]
+fn hex_lines() {
+    let input = "0100dc\n\n060258\r\n20\nzz\n1102\n";
+
+    let results = UplinkStream::new(Trickle(input.as_bytes()), Framing::HexLines).collect::<Vec<_>>();
+    assert_eq!(5, results.len());
+    assert_eq!(Some(22.0), results[0].as_ref().unwrap().temperature());
+    assert_eq!(Some(600), results[1].as_ref().unwrap().co2_ppm());
+    assert!(results[2].is_err());
+    assert!(results[3].is_err());
+    assert_eq!(Some(Occupancy::OccupiedOrHeat), results[4].as_ref().unwrap().occupancy());
+}
+
+#[test]
+fn empty() {
+    assert_eq!(0, UplinkStream::new(&b""[..], Framing::LengthPrefixed).count());
+    assert_eq!(0, UplinkStream::new(&b"\n"[..], Framing::HexLines).count());
+}
@@ -0,0 +1,51 @@
+use super::*;
+
+#[test]
+fn splits_records() {
+    let input = [0x01, 0x00, 0xdc, 0x07, 0x0e, 0x41, 0x11, 0x02, 0x02, 0x36];
+
+    assert_eq!(
+        vec![(0x01, &[0x00, 0xdc][..]), (0x07, &[0x0e, 0x41][..]), (0x11, &[0x02][..]), (0x02, &[0x36][..])],
+        records(&input).collect::<Result<Vec<_>>>().unwrap()
+    );
+    assert_eq!(0, records(&[]).count());
+}
+
+#[test]
+fn unknown_identifier() {
+    let mut iterator = records(&[0x11, 0x02, 0x20, 0x01, 0x00, 0xdc]);
+
+    assert_eq!((0x11, &[0x02][..]), iterator.next().unwrap().unwrap());
+    assert!(iterator.next().unwrap().is_err());
+    assert!(iterator.next().is_none());
+}
+
+#[test]
+fn truncated() {
+    let mut iterator = records(&[0x11, 0x02, 0x01, 0x00]);
+
+    assert!(iterator.next().unwrap().is_ok());
+    assert_eq!(std::io::ErrorKind::InvalidData, iterator.next().unwrap().unwrap_err().kind());
+    assert!(iterator.next().is_none());
+}
+
+#[test]
+fn same_errors_as_deserialize() {
+    for input in [&[0x20][..], &[0x01, 0x00], &[0x11, 0x02, 0x06, 0x02]] {
+        assert_eq!(
+            crate::Uplink::deserialize(input).unwrap_err().to_string(),
+            records(input).find_map(Result::err).unwrap().to_string()
+        );
+    }
+}
+
+#[test]
+fn error_detail() {
+    let input = [0x11, 0x02, 0x06, 0x02];
+
+    assert_eq!(
+        crate::Uplink::deserialize_with(&input, ErrorDetail::Redacted).unwrap_err().to_string(),
+        records_with(&input, ErrorDetail::Redacted).find_map(Result::err).unwrap().to_string()
+    );
+    assert!(!records_with(&input, ErrorDetail::Redacted).find_map(Result::err).unwrap().to_string().contains('['));
+}
@@ -0,0 +1,44 @@
+use super::*;
+
+/// Roughly 11 m per step of latitude.
+fn at(latitude_e4: i32) -> Uplink {
+    Uplink { gps: Some([577_089 + latitude_e4, 119_746]), ..Uplink::default() }
+}
+
+#[test]
+fn legs_between_positions() {
+    let readings = [(0, at(0)), (300, Uplink::default()), (600, at(10)), (1200, at(10))];
+
+    let legs = legs(&readings);
+    assert_eq!(2, legs.len());
+    assert_eq!((0, 600), (legs[0].start_s, legs[0].end_s));
+    assert!((legs[0].distance_m - 111.2).abs() < 0.5);
+    assert_eq!(0.0, legs[1].distance_m);
+}
+
+#[test]
+fn moving_and_stationary() {
+    let mut tracker = MovementTracker::new(MovementConfig::default());
+
+    assert_eq!(None, tracker.push(0, &at(0)));
+    assert_eq!(None, tracker.push(300, &at(1)));
+    assert_eq!(Some(Event::Moving { at_s: 600 }), tracker.push(600, &at(10)));
+    assert_eq!(None, tracker.push(900, &at(20)));
+    assert_eq!(None, tracker.push(1200, &at(21)));
+    assert_eq!(None, tracker.push(1500, &Uplink::default()));
+    assert!(tracker.is_moving());
+    assert_eq!(Some(Event::Stationary { since_s: 900 }), tracker.push(1800, &at(19)));
+    assert!(!tracker.is_moving());
+
+    assert!((tracker.distance_m() - 222.4).abs() < 1.0);
+}
+
+#[test]
+fn jitter_is_not_distance() {
+    let mut tracker = MovementTracker::new(MovementConfig::default());
+    for (i, offset) in [0, 1, -1, 2, 0, -2, 1].iter().enumerate() {
+        assert_eq!(None, tracker.push(i as u64 * 600, &at(*offset)));
+    }
+
+    assert_eq!(0.0, tracker.distance_m());
+}
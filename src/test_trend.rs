@@ -0,0 +1,51 @@
+use super::*;
+
+fn temperature(timestamp_s: u64, temperature: f32) -> (u64, Uplink) {
+    (timestamp_s, Uplink { temperature: Some(temperature), ..Uplink::default() })
+}
+
+fn close(expected: f32, actual: f32) -> bool {
+    (expected - actual).abs() < 1e-3
+}
+
+#[test]
+fn slope() {
+    let readings = [temperature(0, 20.0), temperature(1800, 20.5), (2700, Uplink::default()), temperature(3600, 21.0)];
+
+    assert!(close(1.0, slope_per_hour(&readings, Field::Temperature).unwrap()));
+    assert_eq!(None, slope_per_hour(&readings, Field::Co2));
+    assert_eq!(None, slope_per_hour(&readings[..1], Field::Temperature));
+    assert_eq!(None, slope_per_hour(&[temperature(0, 20.0), temperature(0, 21.0)], Field::Temperature));
+}
+
+#[test]
+fn noisy_slope() {
+    let readings = [temperature(0, 20.0), temperature(600, 20.4), temperature(1200, 20.0), temperature(1800, 20.4)];
+
+    assert!(close(0.48, slope_per_hour(&readings, Field::Temperature).unwrap()));
+}
+
+#[test]
+fn tracker() {
+    let mut tracker = Tracker::new(Field::Co2, 1800, 50.0);
+    let co2 = |co2| Uplink { co2: Some(co2), ..Uplink::default() };
+
+    assert_eq!(None, tracker.push(0, &co2(500)));
+    let trend = tracker.push(600, &co2(600)).unwrap();
+    assert!(close(600.0, trend.slope_per_hour));
+    assert_eq!((Direction::Rising, 600), (trend.direction, trend.since_s));
+
+    assert_eq!((Direction::Rising, 600), tracker.push(1200, &co2(700)).map(|t| (t.direction, t.since_s)).unwrap());
+    assert_eq!(Some(trend.since_s), tracker.push(1500, &Uplink::default()).map(|t| t.since_s));
+
+    // The window drops the early rise, leaving a plateau.
+    tracker.push(1800, &co2(700));
+    tracker.push(2400, &co2(700));
+    let trend = tracker.push(3000, &co2(700)).unwrap();
+    assert_eq!((Direction::Flat, 3000), (trend.direction, trend.since_s));
+
+    tracker.push(3600, &co2(600));
+    let trend = tracker.push(4200, &co2(500)).unwrap();
+    assert_eq!(Direction::Falling, trend.direction);
+    assert_eq!(Some(trend), tracker.trend());
+}
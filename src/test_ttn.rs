@@ -0,0 +1,40 @@
+use super::*;
+
+const EXPORT: &str = r#"{"result":{"end_device_ids":{"device_id":"ers-co2-1","application_ids":{"application_id":"office"},"dev_eui":"A81758FFFE000001","join_eui":"0000000000000000"},"received_at":"2024-03-01T12:34:56.789123456Z","uplink_message":{"f_port":5,"f_cnt":12,"frm_payload":"AQDcBgJY","rx_metadata":[{"gateway_ids":{"gateway_id":"gw-1"},"rssi":-97,"snr":7.5}]}}}
+
+{"result":{"end_device_ids":{"device_id":"ers-co2-2","dev_eui":"A81758FFFE000002"},"received_at":"2024-03-01T13:34:56+01:00","uplink_message":{"f_port":5,"f_cnt":3,"frm_payload":"EQI="}}}
+{"result":{"end_device_ids":{"device_id":"ers-co2-2","dev_eui":"A81758FFFE000002"},"received_at":"2024-03-01T12:40:00Z","uplink_message":{"f_cnt":4}}}
+"#;
+
+#[test]
+fn read_export() {
+    let records = read(EXPORT.as_bytes()).unwrap();
+
+    assert_eq!(vec![
+        Record { timestamp_s: 1_709_296_496, dev_eui: 0xa81758fffe000001, payload: vec![0x01, 0x00, 0xdc, 0x06, 0x02, 0x58] },
+        Record { timestamp_s: 1_709_296_496, dev_eui: 0xa81758fffe000002, payload: vec![0x11, 0x02] },
+    ], records);
+    assert_eq!(Some(600), records[0].uplink().unwrap().co2_ppm());
+}
+
+#[test]
+fn invalid_lines() {
+    let error = read(&b"{\"result\":{}}\n"[..]).unwrap_err();
+    assert_eq!(ErrorKind::InvalidData, error.kind());
+    assert!(error.to_string().starts_with("line 1:"));
+
+    let bad_dev_eui = EXPORT.lines().next().unwrap().replace("A81758FFFE000001", "not-a-dev-eui");
+    assert!(read(bad_dev_eui.as_bytes()).is_err());
+}
+
+#[test]
+fn rfc3339() {
+    assert_eq!(Some(0), parse_rfc3339_s("1970-01-01T00:00:00Z"));
+    assert_eq!(Some(951_782_400), parse_rfc3339_s("2000-02-29T00:00:00Z"));
+    assert_eq!(Some(1_709_296_496), parse_rfc3339_s("2024-03-01T12:34:56.5Z"));
+    assert_eq!(Some(1_709_296_496), parse_rfc3339_s("2024-03-01T10:04:56-02:30"));
+    assert_eq!(None, parse_rfc3339_s("2024-03-01T12:34:56"));
+    assert_eq!(None, parse_rfc3339_s("2024-13-01T12:34:56Z"));
+    assert_eq!(None, parse_rfc3339_s("1969-12-31T23:59:59Z"));
+    assert_eq!(None, parse_rfc3339_s("2024-03-01"));
+}
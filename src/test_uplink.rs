@@ -8,6 +8,19 @@ fn uplink_partial_eq() {
         battery_mv: Some(3809),
         occupancy: Some(Occupancy::OccupiedOrHeat),
         external_digital: Some(false),
+        humidity: Some(45),
+        light: Some(320),
+        motion: Some(3),
+        acceleration: Some([1, -63, 0]),
+        gps: Some([638258, 202630]),
+        pulse1: Some(12),
+        pulse1_absolute: Some(70000),
+        external_temperature_1: Some(4.5),
+        external_temperature_2: Some(-18.2),
+        distance: Some(1250),
+        acceleration_motion: Some(7),
+        waterleak: Some(255),
+        undecoded: Vec::new(),
     };
 
     let uplink1 = uplink0.clone();
@@ -25,6 +38,7 @@ fn test_close() {
 #[test]
 fn deserialize_00() {
     let expected_output = Uplink {
+        motion: Some(1),
         occupancy: Some(Occupancy::PendingOrPir),
         ..Uplink::default()
     };
@@ -39,6 +53,7 @@ fn deserialize_00() {
 fn deserialize_01() {
     let expected_output = Uplink {
         temperature: Some(22.0),
+        humidity: Some(60),
         battery_mv: Some(3649),
         ..Uplink::default()
     };
@@ -53,6 +68,9 @@ fn deserialize_01() {
 fn deserialize_02() {
     let expected_output = Uplink {
         temperature: Some(24.9),
+        humidity: Some(54),
+        light: Some(591),
+        motion: Some(2),
         battery_mv: Some(3658),
         ..Uplink::default()
     };
@@ -67,6 +85,9 @@ fn deserialize_02() {
 fn deserialize_03() {
     let expected_output = Uplink {
         temperature: Some(21.2),
+        humidity: Some(40),
+        light: Some(20),
+        motion: Some(0),
         battery_mv: Some(3613),
         occupancy: Some(Occupancy::PendingOrPir),
         ..Uplink::default()
@@ -78,6 +99,149 @@ fn deserialize_03() {
     )
 }
 
+#[test]
+fn deserialize_humidity() {
+    let uplink = Uplink::deserialize(&[0x02, 0x64, 0x06, 0x02, 0x58, 0x11, 0x02]).unwrap();
+
+    assert_eq!(Some(100), uplink.humidity_percent());
+    assert_eq!(
+        Uplink { humidity: Some(100), co2: Some(600), occupancy: Some(Occupancy::OccupiedOrHeat), ..Uplink::default() },
+        uplink
+    );
+    assert_ne!(Uplink { humidity: Some(99), ..uplink.clone() }, uplink);
+    assert!(Uplink::deserialize(&[0x01, 0x00, 0xdc, 0x02]).is_err());
+}
+
+#[test]
+fn deserialize_light() {
+    let uplink = Uplink::deserialize(&[0x04, 0xff, 0xff, 0x01, 0x00, 0xdc, 0x04, 0x01, 0x2c]).unwrap();
+
+    assert_eq!(Some(300), uplink.light_lux());
+    assert_eq!(Uplink { temperature: Some(22.0), light: Some(300), ..Uplink::default() }, uplink);
+    assert_eq!(Some(65535), Uplink::deserialize(&[0x04, 0xff, 0xff]).unwrap().light_lux());
+    assert!(Uplink::deserialize(&[0x04, 0x01]).is_err());
+}
+
+#[test]
+fn deserialize_motion() {
+    let uplink = Uplink::deserialize(&[0x05, 0xff, 0x06, 0x02, 0x58]).unwrap();
+
+    assert_eq!(Some(255), uplink.motion_count());
+    assert_eq!(Uplink { motion: Some(255), co2: Some(600), ..Uplink::default() }, uplink);
+    assert_ne!(Uplink { motion: Some(0), ..uplink.clone() }, uplink);
+    assert!(Uplink::deserialize(&[0x05]).is_err());
+}
+
+#[test]
+fn deserialize_acceleration() {
+    let uplink = Uplink::deserialize(&[0x03, 0x3f, 0xc1, 0x00, 0x07, 0x0e, 0x10]).unwrap();
+
+    assert_eq!(Some([63, -63, 0]), uplink.acceleration_raw());
+    assert_eq!(Some(Acceleration { x: 1.0, y: -1.0, z: 0.0 }), uplink.acceleration());
+    assert_eq!(Uplink { acceleration: Some([63, -63, 0]), battery_mv: Some(3600), ..Uplink::default() }, uplink);
+    assert_ne!(Uplink { acceleration: Some([63, -63, 1]), ..uplink.clone() }, uplink);
+    assert_eq!(Some([127, -128, -1]), Uplink::deserialize(&[0x03, 0x7f, 0x80, 0xff]).unwrap().acceleration_raw());
+    assert!(Uplink::deserialize(&[0x03, 0x00, 0x00]).is_err());
+}
+
+#[test]
+fn deserialize_gps() {
+    let umea = Uplink::deserialize(&[0x09, 0x32, 0xbd, 0x09, 0x86, 0x17, 0x03, 0x07, 0x0e, 0x10]).unwrap();
+    let buenos_aires = Uplink::deserialize(&[0x09, 0x4b, 0xb8, 0xfa, 0x78, 0x17, 0xf7]).unwrap();
+
+    assert_eq!(Some([638258, 202630]), umea.gps_raw());
+    assert_eq!(Some(GpsPosition { latitude: 63.8258, longitude: 20.263 }), umea.gps());
+    assert_eq!(Some(3600), umea.battery_mv);
+    assert_eq!(Some(GpsPosition { latitude: -34.6037, longitude: -58.3816 }), buenos_aires.gps());
+    assert_eq!(Some([-1, 0]), Uplink::deserialize(&[0x09, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00]).unwrap().gps_raw());
+    assert_ne!(umea, buenos_aires);
+    assert!(Uplink::deserialize(&[0x09, 0x32, 0xbd, 0x09, 0x86, 0x17]).is_err());
+}
+
+#[test]
+fn deserialize_pulse1() {
+    for (relative, absolute) in [(0, 0), (1, 1), (0x1234, 0x1234_5678), (u16::MAX, u32::MAX)] {
+        let input = [&[0x0a][..], &relative.to_be_bytes(), &[0x0b], &absolute.to_be_bytes()].concat();
+        let uplink = Uplink::deserialize(&input).unwrap();
+
+        assert_eq!(Some(relative), uplink.pulse1());
+        assert_eq!(Some(absolute), uplink.pulse1_absolute());
+    }
+    assert_eq!(
+        Uplink { pulse1: Some(5), temperature: Some(22.0), ..Uplink::default() },
+        Uplink::deserialize(&[0x0a, 0x00, 0x05, 0x01, 0x00, 0xdc]).unwrap()
+    );
+    assert!(Uplink::deserialize(&[0x0b, 0x00, 0x00, 0x01]).is_err());
+}
+
+#[test]
+fn deserialize_external_temperature() {
+    let expected_output = Uplink {
+        external_temperature_1: Some(4.5),
+        external_temperature_2: Some(-18.2),
+        ..Uplink::default()
+    };
+
+    assert_eq!(expected_output, Uplink::deserialize(&[0x0c, 0x00, 0x2d, 0x19, 0xff, 0x4a]).unwrap());
+    assert_eq!(Some(-3276.8), Uplink::deserialize(&[0x0c, 0x80, 0x00]).unwrap().value_f64(Field::ExternalTemperature1));
+    assert_eq!(None, Uplink::deserialize(&[0x0c, 0x00, 0x2d]).unwrap().temperature());
+    assert!(Uplink::deserialize(&[0x19, 0x00]).is_err());
+}
+
+#[test]
+fn deserialize_distance() {
+    assert_eq!(Some(1250), Uplink::deserialize(&[0x0e, 0x04, 0xe2]).unwrap().distance_mm());
+    assert_eq!(Some(u16::MAX), Uplink::deserialize(&[0x0e, 0xff, 0xff]).unwrap().distance_mm());
+    assert_eq!(
+        Uplink { distance: Some(300), external_digital: Some(true), ..Uplink::default() },
+        Uplink::deserialize(&[0x0e, 0x01, 0x2c, 0x0d, 0x01]).unwrap()
+    );
+    assert!(Uplink::deserialize(&[0x0e, 0x01]).is_err());
+}
+
+#[test]
+fn deserialize_acceleration_motion() {
+    assert_eq!(Some(0), Uplink::deserialize(&[0x0f, 0x00]).unwrap().acceleration_motion());
+    assert_eq!(Some(255), Uplink::deserialize(&[0x0f, 0xff]).unwrap().acceleration_motion());
+    assert_eq!(
+        Uplink { acceleration_motion: Some(4), motion: Some(1), ..Uplink::default() },
+        Uplink::deserialize(&[0x0f, 0x04, 0x05, 0x01]).unwrap()
+    );
+    assert!(Uplink::deserialize(&[0x0f]).is_err());
+}
+
+#[test]
+fn deserialize_internal_and_external_temperatures() {
+    let combined = Uplink::deserialize(&[0x10, 0x00, 0xdd, 0xff, 0x4a]).unwrap();
+    let separate = Uplink::deserialize(&[0x01, 0x00, 0xdd, 0x0c, 0xff, 0x4a]).unwrap();
+
+    assert_eq!(separate, combined);
+    assert_eq!(Some(22.1), combined.temperature_f64());
+    assert_eq!(Some(-18.2), combined.value_f64(Field::ExternalTemperature1));
+    assert_eq!(None, combined.external_temperature_2());
+    assert!(combined.undecoded_identifiers().is_empty());
+
+    for (internal, external) in [(0x0000, 0x0000), (0x7fff, 0x8000), (0xffff, 0x0001)] {
+        let combined = [&[0x10][..], &u16::to_be_bytes(internal), &u16::to_be_bytes(external)].concat();
+        let separate = [&[0x01][..], &u16::to_be_bytes(internal), &[0x0c], &u16::to_be_bytes(external)].concat();
+
+        assert_eq!(Uplink::deserialize(&separate).unwrap(), Uplink::deserialize(&combined).unwrap());
+    }
+
+    assert!(Uplink::deserialize(&[0x10, 0x00, 0xdd, 0xff]).is_err());
+}
+
+#[test]
+fn deserialize_waterleak() {
+    assert_eq!(Some(0), Uplink::deserialize(&[0x12, 0x00]).unwrap().waterleak());
+    assert_eq!(Some(255), Uplink::deserialize(&[0x12, 0xff]).unwrap().waterleak());
+    assert_eq!(
+        Uplink { waterleak: Some(80), occupancy: Some(Occupancy::NoBody), ..Uplink::default() },
+        Uplink::deserialize(&[0x11, 0x00, 0x12, 0x50]).unwrap()
+    );
+    assert!(Uplink::deserialize(&[0x12]).is_err());
+}
+
 #[test]
 fn deserialize_no_identifier() {
     assert!(Uplink::deserialize(&[0x20, 0x00, 0x00]).is_err());
@@ -88,3 +252,241 @@ fn deserialize_too_short() {
     assert!(Uplink::deserialize(&[0x06, 0x00]).is_err());
     assert!(Uplink::deserialize(&[0x06, 0x00, 0x00]).is_ok());
 }
+
+#[test]
+fn value() {
+    let uplink = Uplink {
+        temperature: Some(22.1),
+        co2: Some(650),
+        battery_mv: Some(3600),
+        occupancy: Some(Occupancy::OccupiedOrHeat),
+        external_digital: Some(true),
+        humidity: Some(45),
+        light: Some(320),
+        motion: Some(3),
+        acceleration: Some([1, -63, 0]),
+        gps: Some([638258, 202630]),
+        pulse1: Some(12),
+        pulse1_absolute: Some(70000),
+        external_temperature_1: Some(4.5),
+        external_temperature_2: Some(-18.2),
+        distance: Some(1250),
+        acceleration_motion: Some(7),
+        waterleak: Some(255),
+        undecoded: Vec::new(),
+    };
+
+    assert_eq!(Some(22.1), uplink.value(Field::Temperature));
+    assert_eq!(Some(650.0), uplink.value(Field::Co2));
+    assert!(close(Some(3.6), uplink.value(Field::Battery), 0.001));
+    assert_eq!(Some(2.0), uplink.value(Field::Occupancy));
+    assert_eq!(Some(1.0), uplink.value(Field::ExternalDigital));
+    assert_eq!(Some(45.0), uplink.value(Field::Humidity));
+    assert_eq!(Some(320.0), uplink.value(Field::Light));
+    assert_eq!(Some(3.0), uplink.value(Field::Motion));
+    assert_eq!(Some(12.0), uplink.value(Field::Pulse1));
+    assert_eq!(Some(70000.0), uplink.value(Field::Pulse1Absolute));
+    assert_eq!(Some(4.5), uplink.value(Field::ExternalTemperature1));
+    assert_eq!(Some(-18.2), uplink.value_f64(Field::ExternalTemperature2));
+    assert_eq!(Some(1250.0), uplink.value(Field::Distance));
+    assert_eq!(Some(7.0), uplink.value(Field::AccelerationMotion));
+    assert_eq!(Some(255.0), uplink.value(Field::Waterleak));
+    assert_eq!(Some(4_294_967_295.0), Uplink { pulse1_absolute: Some(u32::MAX), ..Uplink::default() }.value_f64(Field::Pulse1Absolute));
+    assert_eq!(None, Uplink::default().value(Field::Co2));
+}
+
+#[cfg(feature = "tracing")]
+mod tracing_events {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Level, Metadata, Subscriber};
+
+    #[derive(Default)]
+    struct Counter {
+        spans: AtomicUsize,
+        records: AtomicUsize,
+        warnings: AtomicUsize,
+    }
+
+    struct CountingSubscriber(Arc<Counter>);
+
+    impl Subscriber for CountingSubscriber {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, _: &Attributes<'_>) -> Id {
+            self.0.spans.fetch_add(1, Ordering::SeqCst);
+            Id::from_u64(1)
+        }
+
+        fn record(&self, _: &Id, _: &Record<'_>) {}
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            match *event.metadata().level() {
+                Level::TRACE => self.0.records.fetch_add(1, Ordering::SeqCst),
+                Level::WARN => self.0.warnings.fetch_add(1, Ordering::SeqCst),
+                _ => 0,
+            };
+        }
+
+        fn enter(&self, _: &Id) {}
+
+        fn exit(&self, _: &Id) {}
+    }
+
+    #[test]
+    fn deserialize_emits_events() {
+        let counter = Arc::new(Counter::default());
+        tracing::subscriber::with_default(CountingSubscriber(counter.clone()), || {
+            super::Uplink::deserialize(&[0x01, 0x00, 0xdc, 0x07, 0x0e, 0x41]).unwrap();
+            super::Uplink::deserialize(&[0x20]).unwrap_err();
+        });
+
+        assert_eq!(2, counter.spans.load(Ordering::SeqCst));
+        assert_eq!(2, counter.records.load(Ordering::SeqCst));
+        assert_eq!(1, counter.warnings.load(Ordering::SeqCst));
+    }
+}
+
+#[test]
+fn undecoded_identifiers() {
+    let uplink = Uplink::deserialize(&base64::decode("AQD5AjYEAk8FAgcOSgI2").unwrap()).unwrap();
+
+    assert!(uplink.undecoded_identifiers().is_empty());
+    assert_eq!(
+        &[0x14, 0x08],
+        Uplink::deserialize(&[0x14, 0x00, 0x00, 0x27, 0x10, 0x08, 0x01, 0x02, 0x14, 0x00, 0x00, 0x27, 0x11]).unwrap().undecoded_identifiers()
+    );
+    assert!(Uplink::deserialize(&[0x06, 0x00, 0x00]).unwrap().undecoded_identifiers().is_empty());
+}
+
+#[test]
+fn test_record_size() {
+    assert_eq!(Some(2), record_size(0x01));
+    assert_eq!(Some(65), record_size(0x13));
+    assert_eq!(None, record_size(0x20));
+}
+
+#[test]
+fn layout_checks() {
+    let valid = [
+        Layout { identifier: 0x01, size: 2, name: "Temperature", bin_to: temperature },
+        Layout { identifier: 0x06, size: 2, name: "CO2", bin_to: co2 },
+    ];
+    let zero_size = [Layout { identifier: 0x01, size: 0, name: "Temperature", bin_to: temperature }];
+    let duplicate = [
+        Layout { identifier: 0x01, size: 2, name: "Temperature", bin_to: temperature },
+        Layout { identifier: 0x01, size: 2, name: "CO2", bin_to: co2 },
+    ];
+
+    assert!(no_zero_sizes(&valid) && no_duplicate_identifiers(&valid));
+    assert!(!no_zero_sizes(&zero_size));
+    assert!(!no_duplicate_identifiers(&duplicate));
+    assert!(fields_in_layout(&valid, &[Field::Temperature, Field::Co2]));
+    assert!(!fields_in_layout(&valid, &[Field::Temperature, Field::Battery]));
+}
+
+#[test]
+fn f64_accessors() {
+    let uplink = Uplink::deserialize(&[0x01, 0x00, 0xdd, 0x07, 0x0e, 0x41, 0x06, 0x02, 0x58]).unwrap();
+
+    assert_eq!(Some(22.1), uplink.temperature_f64());
+    assert_eq!(Some(3.649), uplink.battery_voltage_f64());
+    assert_eq!(Some(22.1), uplink.value_f64(Field::Temperature));
+    assert_eq!(Some(3.649), uplink.value_f64(Field::Battery));
+    assert_eq!(Some(600.0), uplink.value_f64(Field::Co2));
+    assert_eq!(None, uplink.value_f64(Field::Occupancy));
+
+    let negative = Uplink::deserialize(&[0x01, 0xff, 0x9c]).unwrap();
+    assert_eq!(Some(-10.0), negative.temperature_f64());
+}
+
+/// Every decoding path must return rather than panic, and agree on which payloads are valid.
+fn check_no_panic(input: &[u8]) {
+    let deserialized = Uplink::deserialize(input);
+    // Framing can succeed where values do not decode, but not the other way round.
+    assert!(deserialized.is_err() || crate::tlv::records(input).all(|record| record.is_ok()));
+
+    let mut parser = crate::push::PushParser::new();
+    let fed = parser.feed(input);
+    let finished = parser.finish();
+    assert_eq!(deserialized.is_ok(), fed.is_ok() && finished.is_ok());
+    if let (Ok(deserialized), Ok(finished)) = (deserialized, finished) {
+        assert_eq!(deserialized, finished);
+    }
+}
+
+#[test]
+fn no_panic_on_short_inputs() {
+    check_no_panic(&[]);
+    for a in 0..=u8::MAX {
+        check_no_panic(&[a]);
+        for b in 0..=u8::MAX {
+            check_no_panic(&[a, b]);
+        }
+    }
+    for layout in LAYOUT {
+        for b in 0..=u8::MAX {
+            for c in 0..=u8::MAX {
+                check_no_panic(&[layout.identifier, b, c]);
+            }
+        }
+    }
+}
+
+#[test]
+fn no_panic_on_random_inputs() {
+    let mut state = 0x2545f4914f6cdd1du64;
+    let mut next = || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+
+    for _ in 0..100_000 {
+        let length = (next() % 64) as usize;
+        // Mostly known identifiers, so that inputs get past the first record.
+        let input = (0..length).map(|_| match next() % 4 { 0 => next() as u8, _ => LAYOUT[(next() % LAYOUT.len() as u64) as usize].identifier }).collect::<Vec<_>>();
+        check_no_panic(&input);
+    }
+}
+
+#[test]
+fn bounded_errors() {
+    let input = [0x01, 0x00, 0xdc, 0x06, 0x02, 0x58, 0x07, 0x0e, 0x10, 0x11, 0x07];
+    let excerpt = |i, detail| Excerpt { input: &input, i, detail }.to_string();
+
+    assert_eq!(format!("{:?}", input), excerpt(9, ErrorDetail::Bytes(11)));
+    assert_eq!("11-byte payload, bytes 3..7 [6, 2, 88, 7]", excerpt(3, ErrorDetail::Bytes(4)));
+    assert_eq!("11-byte payload, bytes 7..11 [14, 16, 17, 7]", excerpt(9, ErrorDetail::Bytes(4)));
+    assert_eq!("11-byte payload", excerpt(9, ErrorDetail::Redacted));
+    assert_eq!("11-byte payload", excerpt(9, ErrorDetail::Bytes(0)));
+
+    let error = Uplink::deserialize(&input).unwrap_err().to_string();
+    assert_eq!(format!("{:?}: index 10 has value 7, which is not an occupancy value", input), error);
+    let error = Uplink::deserialize_with(&input, ErrorDetail::Bytes(4)).unwrap_err().to_string();
+    assert_eq!("11-byte payload, bytes 7..11 [14, 16, 17, 7]: index 10 has value 7, which is not an occupancy value", error);
+    let error = Uplink::deserialize_with(&input, ErrorDetail::Redacted).unwrap_err().to_string();
+    assert_eq!("11-byte payload: index 10 is not an occupancy value", error);
+    let error = Uplink::deserialize_with(&[0x0d, 0x02], ErrorDetail::default()).unwrap_err().to_string();
+    assert!(error.ends_with("index 1 has value 2, which is not a window contact value"), "{}", error);
+    let error = Uplink::deserialize_with(&[0x20, 0x01], ErrorDetail::Redacted).unwrap_err().to_string();
+    assert_eq!("2-byte payload does not look like an Elsys Uplink (index 0 has value 32, which is not an identifier)", error);
+}
+
+#[test]
+fn field_names() {
+    let mut names = Field::ALL.iter().map(|field| field.name()).collect::<Vec<_>>();
+    names.sort();
+    names.dedup();
+
+    assert_eq!(Field::ALL.len(), names.len());
+    assert!(names.iter().all(|name| name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')));
+    assert_eq!("co2_ppm", Field::Co2.name());
+}
@@ -0,0 +1,95 @@
+use super::*;
+
+fn reading(timestamp_s: u64, occupancy: Occupancy) -> (u64, Uplink) {
+    let uplink = Uplink {
+        occupancy: Some(occupancy),
+        ..Uplink::default()
+    };
+    (timestamp_s, uplink)
+}
+
+#[test]
+fn per_hour_splits_across_boundaries() {
+    let readings = [
+        reading(30 * 60, Occupancy::OccupiedOrHeat),
+        reading(HOUR_S + 15 * 60, Occupancy::NoBody),
+        reading(2 * HOUR_S, Occupancy::NoBody),
+    ];
+
+    assert_eq!(
+        vec![
+            Utilization { start_s: 0, occupied_s: 30 * 60, observed_s: 30 * 60 },
+            Utilization { start_s: HOUR_S, occupied_s: 15 * 60, observed_s: HOUR_S },
+        ],
+        per_hour(&readings)
+    );
+}
+
+#[test]
+fn pending_is_not_occupied() {
+    let readings = [
+        reading(0, Occupancy::PendingOrPir),
+        reading(600, Occupancy::OccupiedOrHeat),
+        reading(900, Occupancy::NoBody),
+    ];
+
+    let hours = per_hour(&readings);
+    assert_eq!(1, hours.len());
+    assert_eq!(5, hours[0].occupied_minutes());
+    assert_eq!(Some(300.0 / 900.0), hours[0].ratio());
+}
+
+#[test]
+fn readings_without_occupancy_are_ignored() {
+    let readings = [
+        reading(0, Occupancy::OccupiedOrHeat),
+        (600, Uplink { co2: Some(500), ..Uplink::default() }),
+        reading(1200, Occupancy::NoBody),
+    ];
+
+    assert_eq!(20, per_day(&readings)[0].occupied_minutes());
+}
+
+#[test]
+fn zero_period() {
+    let readings = [
+        reading(0, Occupancy::OccupiedOrHeat),
+        reading(600, Occupancy::NoBody),
+    ];
+
+    assert!(per_period(&readings, 0).is_empty());
+    assert!(per_period_within(&readings, 0, &crate::calendar::Calendar::office(0)).is_empty());
+}
+
+#[test]
+fn peaks_busiest_first() {
+    let periods = [
+        Utilization { start_s: 0, occupied_s: 10, observed_s: 3600 },
+        Utilization { start_s: 3600, occupied_s: 30, observed_s: 3600 },
+        Utilization { start_s: 7200, occupied_s: 20, observed_s: 3600 },
+    ];
+
+    assert_eq!(vec![periods[1], periods[2]], peaks(&periods, 2));
+}
+
+#[test]
+fn per_day_within_calendar() {
+    // Monday 2024-03-04 00:00 UTC, occupied from 17:00 Monday to 09:00 Tuesday, then vacant.
+    let monday_s = 1_709_510_400;
+    let readings = [
+        reading(monday_s + 17 * HOUR_S, Occupancy::OccupiedOrHeat),
+        reading(monday_s + DAY_S + 9 * HOUR_S, Occupancy::NoBody),
+        reading(monday_s + 6 * DAY_S, Occupancy::NoBody),
+    ];
+
+    assert_eq!(
+        vec![
+            Utilization { start_s: monday_s, occupied_s: HOUR_S, observed_s: HOUR_S },
+            Utilization { start_s: monday_s + DAY_S, occupied_s: HOUR_S, observed_s: 10 * HOUR_S },
+            Utilization { start_s: monday_s + 2 * DAY_S, occupied_s: 0, observed_s: 10 * HOUR_S },
+            Utilization { start_s: monday_s + 3 * DAY_S, occupied_s: 0, observed_s: 10 * HOUR_S },
+            Utilization { start_s: monday_s + 4 * DAY_S, occupied_s: 0, observed_s: 10 * HOUR_S },
+        ],
+        per_period_within(&readings, DAY_S, &crate::calendar::Calendar::office(0))
+    );
+}
@@ -0,0 +1,50 @@
+use super::*;
+
+/// An occupied hour, then a vacant one decaying at `ach` towards 420 ppm from 1420 ppm.
+fn readings(start_s: u64, ach: f32) -> Vec<(u64, Uplink)> {
+    let mut output = vec![(start_s, Uplink { co2: Some(1400), occupancy: Some(Occupancy::OccupiedOrHeat), ..Uplink::default() })];
+    for i in 0..=12 {
+        let co2 = 420.0 + 1000.0 * (-ach * i as f32 / 12.0).exp();
+        let occupancy = if i == 0 { Some(Occupancy::NoBody) } else { None };
+        output.push((start_s + 3600 + i * 300, Uplink { co2: Some(co2.round() as u16), occupancy, ..Uplink::default() }));
+    }
+    output
+}
+
+#[test]
+fn single_decay() {
+    let decays = decays(&readings(0, 1.5), &DecayConfig::default());
+
+    assert_eq!(1, decays.len());
+    assert_eq!((3600, 7200), (decays[0].start_s, decays[0].end_s));
+    assert!((1.5 - decays[0].air_changes_per_hour).abs() < 0.01);
+}
+
+#[test]
+fn median_of_decays() {
+    let readings = [readings(0, 1.0), readings(10_000, 2.0), readings(20_000, 4.0)].concat();
+
+    let decays = decays(&readings, &DecayConfig::default());
+    assert_eq!(3, decays.len());
+    assert!((2.0 - air_changes_per_hour(&decays).unwrap()).abs() < 0.02);
+    assert_eq!(None, air_changes_per_hour(&[]));
+}
+
+#[test]
+fn too_short_or_too_close_to_outdoor() {
+    let readings = readings(0, 1.5);
+
+    assert!(decays(&readings[..6], &DecayConfig::default()).is_empty());
+    assert!(decays(&readings, &DecayConfig { min_excess_ppm: 900.0, ..DecayConfig::default() }).is_empty());
+}
+
+#[test]
+fn ended_by_occupancy() {
+    let mut readings = readings(0, 1.5);
+    readings.push((7500, Uplink { co2: Some(900), occupancy: Some(Occupancy::OccupiedOrHeat), ..Uplink::default() }));
+    readings.push((7800, Uplink { co2: Some(500), ..Uplink::default() }));
+
+    let decays = decays(&readings, &DecayConfig::default());
+    assert_eq!(1, decays.len());
+    assert_eq!(7200, decays[0].end_s);
+}
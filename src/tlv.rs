@@ -0,0 +1,60 @@
+#![deny(
+    clippy::indexing_slicing,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic
+)]
+
+use std::io::Result;
+
+use crate::uplink::{record_size, verify_array_length, verify_pattern_matches};
+use crate::ErrorDetail;
+
+/// Splits a payload into its records, each an identifier and the bytes of its value, without
+/// decoding the values. After an unknown identifier or a truncated record the iterator yields the
+/// error and ends.
+pub fn records(input: &[u8]) -> Records<'_> {
+    records_with(input, ErrorDetail::default())
+}
+
+/// As `records`, quoting the payload in errors only as far as `detail` allows.
+pub fn records_with(input: &[u8], detail: ErrorDetail) -> Records<'_> {
+    Records {
+        input,
+        i: 0,
+        detail,
+    }
+}
+
+pub struct Records<'a> {
+    input: &'a [u8],
+    i: usize,
+    detail: ErrorDetail,
+}
+
+impl<'a> Iterator for Records<'a> {
+    type Item = Result<(u8, &'a [u8])>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let identifier = *self.input.get(self.i)?;
+        let i = self.i;
+        self.i = self.input.len();
+
+        let fault = |fault: crate::uplink::Fault| Err(fault.into_error(self.input, self.detail));
+        let Some(size) = record_size(identifier) else {
+            return verify_pattern_matches(i, false).err().map(fault);
+        };
+        let Some(value) = self.input.get(i + 1..=i + size) else {
+            return verify_array_length(self.input, i, size).err().map(fault);
+        };
+
+        self.i = i + 1 + size;
+        Some(Ok((identifier, value)))
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[allow(clippy::indexing_slicing, clippy::unwrap_used)]
+#[path = "./test_tlv.rs"]
+mod test_tlv;
@@ -0,0 +1,125 @@
+use crate::{GpsPosition, Uplink};
+
+/// The straight-line distance between two successive positions.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Leg {
+    pub start_s: u64,
+    pub end_s: u64,
+    pub distance_m: f64,
+}
+
+/// Legs between successive positions in `readings`, which must be sorted by timestamp. Readings
+/// without a position are skipped. GPS jitter is counted as movement; `MovementTracker` filters it.
+pub fn legs(readings: &[(u64, Uplink)]) -> Vec<Leg> {
+    let positions = readings
+        .iter()
+        .filter_map(|(timestamp_s, uplink)| Some((*timestamp_s, uplink.gps()?)))
+        .collect::<Vec<_>>();
+    positions
+        .windows(2)
+        .filter_map(|pair| match pair {
+            [(start_s, from), (end_s, to)] => Some(Leg {
+                start_s: *start_s,
+                end_s: *end_s,
+                distance_m: from.distance_m(to),
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MovementConfig {
+    /// Positions closer than this to the last accepted one are taken as GPS jitter.
+    pub jitter_m: f64,
+    /// How long without movement before a moving device counts as stationary.
+    pub stationary_after_s: u64,
+}
+
+impl Default for MovementConfig {
+    fn default() -> Self {
+        Self {
+            jitter_m: 25.0,
+            stationary_after_s: 15 * 60,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Event {
+    Moving {
+        at_s: u64,
+    },
+    /// No movement since `since_s`.
+    Stationary {
+        since_s: u64,
+    },
+}
+
+/// Classifies one device as moving or stationary and totals the distance it travels, ignoring
+/// movements within `jitter_m` of the last accepted position. Devices start stationary. Uplinks
+/// must be pushed in chronological order, and those without a position are ignored.
+#[derive(Clone, Debug)]
+pub struct MovementTracker {
+    config: MovementConfig,
+    anchor: Option<GpsPosition>,
+    moved_s: u64,
+    moving: bool,
+    distance_m: f64,
+}
+
+impl MovementTracker {
+    pub fn new(config: MovementConfig) -> Self {
+        Self {
+            config,
+            anchor: None,
+            moved_s: 0,
+            moving: false,
+            distance_m: 0.0,
+        }
+    }
+
+    pub fn is_moving(&self) -> bool {
+        self.moving
+    }
+
+    /// Distance travelled between accepted positions.
+    pub fn distance_m(&self) -> f64 {
+        self.distance_m
+    }
+
+    pub fn push(&mut self, timestamp_s: u64, uplink: &Uplink) -> Option<Event> {
+        let position = uplink.gps()?;
+        let Some(anchor) = self.anchor else {
+            self.anchor = Some(position);
+            self.moved_s = timestamp_s;
+            return None;
+        };
+
+        let distance_m = anchor.distance_m(&position);
+        if distance_m > self.config.jitter_m {
+            self.anchor = Some(position);
+            self.moved_s = timestamp_s;
+            self.distance_m += distance_m;
+            if !self.moving {
+                self.moving = true;
+                return Some(Event::Moving { at_s: timestamp_s });
+            }
+        } else if self.moving
+            && timestamp_s.saturating_sub(self.moved_s) >= self.config.stationary_after_s
+        {
+            self.moving = false;
+            return Some(Event::Stationary {
+                since_s: self.moved_s,
+            });
+        }
+
+        None
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[path = "./test_tracking.rs"]
+mod test_tracking;
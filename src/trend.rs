@@ -0,0 +1,120 @@
+use std::collections::VecDeque;
+
+use crate::{Field, Uplink};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Direction {
+    Rising,
+    Falling,
+    Flat,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Trend {
+    /// Least-squares slope, in the units of the field's accessor per hour.
+    pub slope_per_hour: f32,
+    pub direction: Direction,
+    /// When the trend took its current direction. Equal to the latest timestamp when the direction
+    /// has just changed.
+    pub since_s: u64,
+}
+
+/// Least-squares slope of a field over the readings, per hour. Needs two readings of the field at
+/// different times.
+pub fn slope_per_hour(readings: &[(u64, Uplink)], field: Field) -> Option<f32> {
+    let points = readings
+        .iter()
+        .filter_map(|(timestamp_s, uplink)| Some((*timestamp_s, uplink.value(field)?)))
+        .collect::<Vec<_>>();
+    fit(points.iter().copied())
+}
+
+/// Least-squares slope of values over time, per hour.
+pub(crate) fn fit(points: impl Iterator<Item = (u64, f32)> + Clone) -> Option<f32> {
+    let origin_s = points.clone().next()?.0;
+    let (mut n, mut sum_t, mut sum_v) = (0.0, 0.0, 0.0);
+    for (timestamp_s, value) in points.clone() {
+        n += 1.0;
+        sum_t += timestamp_s.wrapping_sub(origin_s) as i64 as f64 / 3600.0;
+        sum_v += value as f64;
+    }
+    let (mean_t, mean_v) = (sum_t / n, sum_v / n);
+
+    let (mut covariance, mut variance) = (0.0, 0.0);
+    for (timestamp_s, value) in points {
+        let t = timestamp_s.wrapping_sub(origin_s) as i64 as f64 / 3600.0 - mean_t;
+        covariance += t * (value as f64 - mean_v);
+        variance += t * t;
+    }
+
+    (variance > 0.0).then(|| (covariance / variance) as f32)
+}
+
+/// Follows the trend of one field of one device over a sliding window. Uplinks must be pushed in
+/// chronological order.
+#[derive(Clone, Debug)]
+pub struct Tracker {
+    field: Field,
+    window_s: u64,
+    /// Slopes no steeper than this, either way, count as flat.
+    flat_per_hour: f32,
+    history: VecDeque<(u64, f32)>,
+    current: Option<Trend>,
+}
+
+impl Tracker {
+    pub fn new(field: Field, window_s: u64, flat_per_hour: f32) -> Self {
+        Self {
+            field,
+            window_s,
+            flat_per_hour,
+            history: VecDeque::new(),
+            current: None,
+        }
+    }
+
+    pub fn trend(&self) -> Option<Trend> {
+        self.current
+    }
+
+    pub fn push(&mut self, timestamp_s: u64, uplink: &Uplink) -> Option<Trend> {
+        let Some(value) = uplink.value(self.field) else {
+            return self.current;
+        };
+        self.history.push_back((timestamp_s, value));
+        while self
+            .history
+            .front()
+            .is_some_and(|(oldest_s, _)| timestamp_s.saturating_sub(*oldest_s) > self.window_s)
+        {
+            self.history.pop_front();
+        }
+
+        let Some(slope_per_hour) = fit(self.history.iter().copied()) else {
+            return self.current;
+        };
+        let direction = if slope_per_hour > self.flat_per_hour {
+            Direction::Rising
+        } else if slope_per_hour < -self.flat_per_hour {
+            Direction::Falling
+        } else {
+            Direction::Flat
+        };
+        let since_s = match self.current {
+            Some(current) if current.direction == direction => current.since_s,
+            _ => timestamp_s,
+        };
+
+        self.current = Some(Trend {
+            slope_per_hour,
+            direction,
+            since_s,
+        });
+        self.current
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[path = "./test_trend.rs"]
+mod test_trend;
@@ -0,0 +1,74 @@
+use std::io::{BufRead, BufReader, Error, ErrorKind, Read, Result};
+
+use serde::Deserialize;
+
+use crate::calendar::parse_rfc3339_s;
+use crate::record::Record;
+
+#[derive(Deserialize)]
+struct Line {
+    result: Message,
+}
+
+#[derive(Deserialize)]
+struct Message {
+    end_device_ids: DeviceIds,
+    received_at: String,
+    uplink_message: UplinkMessage,
+}
+
+#[derive(Deserialize)]
+struct DeviceIds {
+    dev_eui: String,
+}
+
+#[derive(Deserialize)]
+struct UplinkMessage {
+    frm_payload: Option<String>,
+}
+
+/// Reads the uplinks of a The Things Stack Storage Integration export, one JSON object per line as
+/// returned by its `/packages/storage/uplink_message` API. Messages without an application payload
+/// are skipped.
+pub fn read<R: Read>(reader: R) -> Result<Vec<Record>> {
+    let mut output = Vec::new();
+
+    for (number, line) in BufReader::new(reader).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let invalid = |message: String| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("line {}: {}", number + 1, message),
+            )
+        };
+
+        let message = serde_json::from_str::<Line>(&line)
+            .map_err(|e| invalid(e.to_string()))?
+            .result;
+        let Some(frm_payload) = message.uplink_message.frm_payload else {
+            continue;
+        };
+
+        output.push(Record {
+            timestamp_s: parse_rfc3339_s(&message.received_at)
+                .ok_or_else(|| invalid(format!("{:?} is not a timestamp", message.received_at)))?,
+            dev_eui: u64::from_str_radix(&message.end_device_ids.dev_eui, 16).map_err(|_| {
+                invalid(format!(
+                    "{:?} is not a DevEUI",
+                    message.end_device_ids.dev_eui
+                ))
+            })?,
+            payload: base64::decode(&frm_payload).map_err(|e| invalid(e.to_string()))?,
+        });
+    }
+
+    Ok(output)
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[path = "./test_ttn.rs"]
+mod test_ttn;
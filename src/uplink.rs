@@ -1,21 +1,188 @@
+// Decoding runs on untrusted radio input, so it must not panic whatever the payload.
+#![deny(
+    clippy::indexing_slicing,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic
+)]
+
+use std::fmt;
 use std::io::{Error, ErrorKind, Result};
 
 #[derive(Clone, Debug, Default)]
 pub struct Uplink {
-    temperature: Option<f32>,
-    co2: Option<u16>,
-    battery_mv: Option<u16>,
-    occupancy: Option<Occupancy>,
-    external_digital: Option<bool>,
+    pub(crate) temperature: Option<f32>,
+    pub(crate) co2: Option<u16>,
+    pub(crate) battery_mv: Option<u16>,
+    pub(crate) occupancy: Option<Occupancy>,
+    pub(crate) external_digital: Option<bool>,
+    pub(crate) humidity: Option<u8>,
+    pub(crate) light: Option<u16>,
+    pub(crate) motion: Option<u8>,
+    pub(crate) pulse1: Option<u16>,
+    pub(crate) pulse1_absolute: Option<u32>,
+    pub(crate) external_temperature_1: Option<f32>,
+    pub(crate) external_temperature_2: Option<f32>,
+    pub(crate) distance: Option<u16>,
+    pub(crate) acceleration_motion: Option<u8>,
+    pub(crate) waterleak: Option<u8>,
+    pub(crate) acceleration: Option<[i8; 3]>,
+    pub(crate) gps: Option<[i32; 2]>,
+    pub(crate) undecoded: Vec<u8>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum Occupancy {
     NoBody,
     PendingOrPir,
     OccupiedOrHeat,
 }
 
+/// How much of a payload decode errors quote. Errors always keep offsets and identifiers, and keep
+/// the offending value unless redacted.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ErrorDetail {
+    /// Whole payloads up to this many bytes, otherwise this many bytes from the offending record.
+    Bytes(usize),
+    /// Only the payload length. `Bytes(0)` is the same.
+    Redacted,
+}
+
+impl Default for ErrorDetail {
+    fn default() -> Self {
+        Self::Bytes(16)
+    }
+}
+
+/// Acceleration along each axis, in G.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Acceleration {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+}
+
+impl Acceleration {
+    /// Raw acceleration per G.
+    const PER_G: f32 = 63.0;
+
+    pub(crate) fn from_raw([x, y, z]: [i8; 3]) -> Self {
+        Self {
+            x: x as f32 / Self::PER_G,
+            y: y as f32 / Self::PER_G,
+            z: z as f32 / Self::PER_G,
+        }
+    }
+}
+
+/// A position in degrees, negative in the southern and western hemispheres.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GpsPosition {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+impl GpsPosition {
+    /// Raw position units per degree.
+    const PER_DEGREE: f64 = 10_000.0;
+
+    pub(crate) fn from_raw([latitude, longitude]: [i32; 2]) -> Self {
+        Self {
+            latitude: latitude as f64 / Self::PER_DEGREE,
+            longitude: longitude as f64 / Self::PER_DEGREE,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Field {
+    Temperature,
+    Co2,
+    Battery,
+    Occupancy,
+    ExternalDigital,
+    Humidity,
+    Light,
+    Motion,
+    Pulse1,
+    Pulse1Absolute,
+    ExternalTemperature1,
+    ExternalTemperature2,
+    Distance,
+    AccelerationMotion,
+    Waterleak,
+}
+
+impl Field {
+    pub const ALL: &'static [Field] = &[
+        Field::Temperature,
+        Field::Co2,
+        Field::Battery,
+        Field::Occupancy,
+        Field::ExternalDigital,
+        Field::Humidity,
+        Field::Light,
+        Field::Motion,
+        Field::Pulse1,
+        Field::Pulse1Absolute,
+        Field::ExternalTemperature1,
+        Field::ExternalTemperature2,
+        Field::Distance,
+        Field::AccelerationMotion,
+        Field::Waterleak,
+    ];
+
+    /// The field's name in snake case, ending in its unit where the accessor's name does, such as
+    /// `co2_ppm`. Unlike the serde name, it is meant for metric and object names.
+    pub fn name(self) -> &'static str {
+        match self {
+            Field::Temperature => "temperature",
+            Field::Co2 => "co2_ppm",
+            Field::Battery => "battery_voltage",
+            Field::Occupancy => "occupancy",
+            Field::ExternalDigital => "external_digital",
+            Field::Humidity => "humidity_percent",
+            Field::Light => "light_lux",
+            Field::Motion => "motion_count",
+            Field::Pulse1 => "pulse1",
+            Field::Pulse1Absolute => "pulse1_absolute",
+            Field::ExternalTemperature1 => "external_temperature_1",
+            Field::ExternalTemperature2 => "external_temperature_2",
+            Field::Distance => "distance_mm",
+            Field::AccelerationMotion => "acceleration_motion",
+            Field::Waterleak => "waterleak",
+        }
+    }
+
+    /// The identifier of the record the field is sent in.
+    pub(crate) const fn identifier(self) -> u8 {
+        match self {
+            Field::Temperature => 0x01,
+            Field::Humidity => 0x02,
+            Field::Light => 0x04,
+            Field::Motion => 0x05,
+            Field::Pulse1 => 0x0a,
+            Field::Pulse1Absolute => 0x0b,
+            Field::ExternalTemperature1 => 0x0c,
+            Field::ExternalTemperature2 => 0x19,
+            Field::Distance => 0x0e,
+            Field::AccelerationMotion => 0x0f,
+            Field::Waterleak => 0x12,
+            Field::Co2 => 0x06,
+            Field::Battery => 0x07,
+            Field::ExternalDigital => 0x0d,
+            Field::Occupancy => 0x11,
+        }
+    }
+}
+
+/// Compares decoded values only; `undecoded_identifiers` is not compared.
 impl PartialEq for Uplink {
     fn eq(&self, other: &Self) -> bool {
         close(self.temperature, other.temperature, 0.1)
@@ -23,9 +190,33 @@ impl PartialEq for Uplink {
             && self.battery_mv == other.battery_mv
             && self.occupancy == other.occupancy
             && self.external_digital == other.external_digital
+            && self.humidity == other.humidity
+            && self.light == other.light
+            && self.motion == other.motion
+            && self.pulse1 == other.pulse1
+            && self.pulse1_absolute == other.pulse1_absolute
+            && close(
+                self.external_temperature_1,
+                other.external_temperature_1,
+                0.1,
+            )
+            && close(
+                self.external_temperature_2,
+                other.external_temperature_2,
+                0.1,
+            )
+            && self.distance == other.distance
+            && self.acceleration_motion == other.acceleration_motion
+            && self.waterleak == other.waterleak
+            && self.acceleration == other.acceleration
+            && self.gps == other.gps
     }
 }
 
+fn tenths(value: f32) -> f64 {
+    (value as f64 * 10.0).round() / 10.0
+}
+
 fn close(x: Option<f32>, y: Option<f32>, resolution: f32) -> bool {
     match (x, y) {
         (Some(a), Some(b)) => (a - b) * 2.0 < resolution && (b - a) * 2.0 < resolution,
@@ -35,53 +226,125 @@ fn close(x: Option<f32>, y: Option<f32>, resolution: f32) -> bool {
 }
 
 struct Layout {
-    bin_to: fn(&[u8], usize, &mut Uplink) -> Result<()>,
+    bin_to: fn(&[u8], usize, &mut Uplink) -> Decoded,
     identifier: u8,
+    name: &'static str,
     size: usize,
 }
 
 #[rustfmt::skip]
 const LAYOUT: &[Layout] = &[
-    Layout { identifier: 0x01, size: 2, bin_to: temperature },     //                         -3276.8°C --> 3276.7°C
-    Layout { identifier: 0x02, size: 1, bin_to: no_decode },       // Humidity              ; 0-100%
-    Layout { identifier: 0x03, size: 3, bin_to: no_decode },       // Acceleration          ; X,Y,Z -128 --> 127 +/-63=1G
-    Layout { identifier: 0x04, size: 2, bin_to: no_decode },       // Light                 ; 0 --> 65535 Lux
-    Layout { identifier: 0x05, size: 1, bin_to: no_decode },       // Motion                ; No of motion 0-255
-    Layout { identifier: 0x06, size: 2, bin_to: co2 },             //                         0-65535 ppm
-    Layout { identifier: 0x07, size: 2, bin_to: battery },         //                       ; 0-65535mV
-    Layout { identifier: 0x08, size: 2, bin_to: no_decode },       // Analog1               ; 0-65535mV
-    Layout { identifier: 0x09, size: 6, bin_to: no_decode },       // GPS                   ; latitude & longitude
-    Layout { identifier: 0x0a, size: 2, bin_to: no_decode },       // Pulse1                ; relative pulse count
-    Layout { identifier: 0x0b, size: 4, bin_to: no_decode },       // PulseAbs              ; no 0 --> 0xFFFFFFFF
-    Layout { identifier: 0x0c, size: 2, bin_to: no_decode },       // External Temperature 1; -3276.5C --> 3276.5C
-    Layout { identifier: 0x0d, size: 1, bin_to: external_digital },//                         1 or 0
-    Layout { identifier: 0x0e, size: 2, bin_to: no_decode },       // External Distance     ; mm
-    Layout { identifier: 0x0f, size: 1, bin_to: no_decode },       // Acceleration Motion   ; number of vibration/motion
-    Layout { identifier: 0x10, size: 4, bin_to: no_decode },       // Internal And External Temperatures; -3276.5C --> 3276.5C
-    Layout { identifier: 0x11, size: 1, bin_to: occupancy },       // Occupancy
-    Layout { identifier: 0x12, size: 1, bin_to: no_decode },       // Waterleak             ; 0-255
-    Layout { identifier: 0x13, size: 65, bin_to: no_decode },      // Grideye               ; 1 byte ref + 64 bytes external temperature
-    Layout { identifier: 0x14, size: 4, bin_to: no_decode },       // Pressure              ; hPa
-    Layout { identifier: 0x15, size: 2, bin_to: no_decode },       // Sound                 ; peak/avg
-    Layout { identifier: 0x16, size: 2, bin_to: no_decode },       // Pulse2                ; 0 --> 0xFFFF
-    Layout { identifier: 0x17, size: 4, bin_to: no_decode },       // Pulse2 Abs            ; No 0 --> 0xFFFFFFFF
-    Layout { identifier: 0x18, size: 2, bin_to: no_decode },       // Analog2               ; Voltage in mV
-    Layout { identifier: 0x19, size: 2, bin_to: no_decode },       // External Temperature2 ; -3276.5C --> 3276.5C
-    Layout { identifier: 0x1a, size: 1, bin_to: no_decode },       // External Digital2     ; 1 or 0
-    Layout { identifier: 0x1b, size: 4, bin_to: no_decode },       // External Analog       ; uV
-    Layout { identifier: 0x1c, size: 2, bin_to: no_decode },       // TVOC                  ; ppb
-    Layout { identifier: 0x3d, size: 4, bin_to: no_decode },       // Debug
+    Layout { identifier: 0x01, size: 2, name: "Temperature", bin_to: temperature },                      // -3276.8°C --> 3276.7°C
+    Layout { identifier: 0x02, size: 1, name: "Humidity", bin_to: humidity },                            // 0-100%
+    Layout { identifier: 0x03, size: 3, name: "Acceleration", bin_to: acceleration },                    // X,Y,Z -128 --> 127 +/-63=1G
+    Layout { identifier: 0x04, size: 2, name: "Light", bin_to: light },                                  // 0 --> 65535 Lux
+    Layout { identifier: 0x05, size: 1, name: "Motion", bin_to: motion },                                // No of motion 0-255
+    Layout { identifier: 0x06, size: 2, name: "CO2", bin_to: co2 },                                      // 0-65535 ppm
+    Layout { identifier: 0x07, size: 2, name: "Battery", bin_to: battery },                              // 0-65535mV
+    Layout { identifier: 0x08, size: 2, name: "Analog1", bin_to: no_decode },                            // 0-65535mV
+    Layout { identifier: 0x09, size: 6, name: "GPS", bin_to: gps },                                      // latitude & longitude
+    Layout { identifier: 0x0a, size: 2, name: "Pulse1", bin_to: pulse1 },                                // relative pulse count
+    Layout { identifier: 0x0b, size: 4, name: "PulseAbs", bin_to: pulse1_absolute },                     // no 0 --> 0xFFFFFFFF
+    Layout { identifier: 0x0c, size: 2, name: "External Temperature 1", bin_to: external_temperature_1 }, // -3276.5C --> 3276.5C
+    Layout { identifier: 0x0d, size: 1, name: "External Digital", bin_to: external_digital },            // 1 or 0
+    Layout { identifier: 0x0e, size: 2, name: "External Distance", bin_to: distance },                   // mm
+    Layout { identifier: 0x0f, size: 1, name: "Acceleration Motion", bin_to: acceleration_motion },      // number of vibration/motion
+    Layout { identifier: 0x10, size: 4, name: "Internal And External Temperatures", bin_to: internal_and_external_temperatures }, // -3276.5C --> 3276.5C
+    Layout { identifier: 0x11, size: 1, name: "Occupancy", bin_to: occupancy },
+    Layout { identifier: 0x12, size: 1, name: "Waterleak", bin_to: waterleak },                          // 0-255
+    Layout { identifier: 0x13, size: 65, name: "Grideye", bin_to: no_decode },                           // 1 byte ref + 64 bytes external temperature
+    Layout { identifier: 0x14, size: 4, name: "Pressure", bin_to: no_decode },                           // hPa
+    Layout { identifier: 0x15, size: 2, name: "Sound", bin_to: no_decode },                              // peak/avg
+    Layout { identifier: 0x16, size: 2, name: "Pulse2", bin_to: no_decode },                             // 0 --> 0xFFFF
+    Layout { identifier: 0x17, size: 4, name: "Pulse2 Abs", bin_to: no_decode },                         // No 0 --> 0xFFFFFFFF
+    Layout { identifier: 0x18, size: 2, name: "Analog2", bin_to: no_decode },                            // Voltage in mV
+    Layout { identifier: 0x19, size: 2, name: "External Temperature 2", bin_to: external_temperature_2 }, // -3276.5C --> 3276.5C
+    Layout { identifier: 0x1a, size: 1, name: "External Digital2", bin_to: no_decode },                  // 1 or 0
+    Layout { identifier: 0x1b, size: 4, name: "External Analog", bin_to: no_decode },                    // uV
+    Layout { identifier: 0x1c, size: 2, name: "TVOC", bin_to: no_decode },                               // ppb
+    Layout { identifier: 0x3d, size: 4, name: "Debug", bin_to: no_decode },
 ];
 
+const _: () = assert!(no_zero_sizes(LAYOUT), "LAYOUT has a record of size 0");
+const _: () = assert!(
+    no_duplicate_identifiers(LAYOUT),
+    "LAYOUT has a duplicate identifier"
+);
+const _: () = assert!(
+    fields_in_layout(LAYOUT, Field::ALL),
+    "a Field is sent in a record missing from LAYOUT"
+);
+
+const fn no_zero_sizes(layout: &[Layout]) -> bool {
+    match layout {
+        [] => true,
+        [first, rest @ ..] => first.size != 0 && no_zero_sizes(rest),
+    }
+}
+
+const fn no_duplicate_identifiers(layout: &[Layout]) -> bool {
+    match layout {
+        [] => true,
+        [first, rest @ ..] => {
+            !has_identifier(rest, first.identifier) && no_duplicate_identifiers(rest)
+        }
+    }
+}
+
+const fn fields_in_layout(layout: &[Layout], fields: &[Field]) -> bool {
+    match fields {
+        [] => true,
+        [first, rest @ ..] => {
+            has_identifier(layout, first.identifier()) && fields_in_layout(layout, rest)
+        }
+    }
+}
+
+const fn has_identifier(layout: &[Layout], identifier: u8) -> bool {
+    match layout {
+        [] => false,
+        [first, rest @ ..] => first.identifier == identifier || has_identifier(rest, identifier),
+    }
+}
+
 impl Uplink {
     pub fn deserialize(input: &[u8]) -> Result<Self> {
+        Self::deserialize_with(input, ErrorDetail::default())
+    }
+
+    /// As `deserialize`, quoting the payload in errors only as far as `detail` allows, so that
+    /// payloads can be kept out of logs and error trackers.
+    pub fn deserialize_with(input: &[u8], detail: ErrorDetail) -> Result<Self> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("elsys_deserialize", length = input.len()).entered();
+
+        let output =
+            Self::deserialize_records(input).map_err(|fault| fault.into_error(input, detail));
+
+        #[cfg(feature = "tracing")]
+        match &output {
+            Ok(uplink) => tracing::debug!(?uplink, "deserialized"),
+            Err(error) => tracing::warn!(%error, "deserialization failed"),
+        }
+
+        output
+    }
+
+    fn deserialize_records(input: &[u8]) -> Decoded<Self> {
         let mut output = Self::default();
 
         let mut i = 0;
-        while i < input.len() {
+        while let Some(value) = input.get(i) {
             let mut identifier_found = false;
             for deserialise_pattern in LAYOUT {
-                if input[i] == deserialise_pattern.identifier {
+                if *value == deserialise_pattern.identifier {
+                    #[cfg(feature = "tracing")]
+                    tracing::trace!(
+                        index = i,
+                        identifier = deserialise_pattern.identifier,
+                        size = deserialise_pattern.size,
+                        "record"
+                    );
                     identifier_found = true;
                     verify_array_length(input, i, deserialise_pattern.size)?;
                     (deserialise_pattern.bin_to)(input, i + 1, &mut output)?;
@@ -90,7 +353,7 @@ impl Uplink {
                 }
             }
 
-            verify_pattern_matches(input, i, identifier_found)?;
+            verify_pattern_matches(i, identifier_found)?;
 
             i += 1;
         }
@@ -117,94 +380,425 @@ impl Uplink {
     pub fn occupancy(&self) -> Option<Occupancy> {
         self.occupancy
     }
+
+    /// Relative humidity, 0 to 100 %.
+    pub fn humidity_percent(&self) -> Option<u8> {
+        self.humidity
+    }
+
+    pub fn light_lux(&self) -> Option<u16> {
+        self.light
+    }
+
+    /// Motion events counted by the PIR sensor since the previous uplink.
+    pub fn motion_count(&self) -> Option<u8> {
+        self.motion
+    }
+
+    /// Pulses counted on pulse input 1 since the previous uplink.
+    pub fn pulse1(&self) -> Option<u16> {
+        self.pulse1
+    }
+
+    /// The running total of pulses on pulse input 1.
+    pub fn pulse1_absolute(&self) -> Option<u32> {
+        self.pulse1_absolute
+    }
+
+    /// The temperature measured by external probe 1, in °C.
+    pub fn external_temperature_1(&self) -> Option<f32> {
+        self.external_temperature_1
+    }
+
+    /// The temperature measured by external probe 2, in °C.
+    pub fn external_temperature_2(&self) -> Option<f32> {
+        self.external_temperature_2
+    }
+
+    /// The distance measured by an external ultrasonic sensor, in millimetres.
+    pub fn distance_mm(&self) -> Option<u16> {
+        self.distance
+    }
+
+    /// Vibration or motion events counted by the accelerometer since the previous uplink.
+    pub fn acceleration_motion(&self) -> Option<u8> {
+        self.acceleration_motion
+    }
+
+    /// The raw reading of the waterleak sensor, from 0 when dry to 255.
+    pub fn waterleak(&self) -> Option<u8> {
+        self.waterleak
+    }
+
+    pub fn acceleration(&self) -> Option<Acceleration> {
+        self.acceleration.map(Acceleration::from_raw)
+    }
+
+    /// Acceleration along the X, Y and Z axes as sent, where 63 is 1 G.
+    pub fn acceleration_raw(&self) -> Option<[i8; 3]> {
+        self.acceleration
+    }
+
+    pub fn gps(&self) -> Option<GpsPosition> {
+        self.gps.map(GpsPosition::from_raw)
+    }
+
+    /// Latitude and longitude as sent, in ten-thousandths of a degree.
+    pub fn gps_raw(&self) -> Option<[i32; 2]> {
+        self.gps
+    }
+
+    /// Identifiers which were present in the payload and recognised, but which this crate does not
+    /// decode yet, in order of first appearance.
+    pub fn undecoded_identifiers(&self) -> &[u8] {
+        &self.undecoded
+    }
+
+    /// The temperature as an `f64`, without the rounding error the `f32` accessor carries beyond
+    /// its 0.1 °C resolution.
+    pub fn temperature_f64(&self) -> Option<f64> {
+        self.temperature.map(tenths)
+    }
+
+    pub fn battery_voltage_f64(&self) -> Option<f64> {
+        self.battery_mv.map(|bmv| bmv as f64 / 1000.0)
+    }
+
+    /// As `value`, with the precision of the `f64` accessors.
+    pub fn value_f64(&self, field: Field) -> Option<f64> {
+        match field {
+            Field::Temperature => self.temperature_f64(),
+            Field::Battery => self.battery_voltage_f64(),
+            Field::Pulse1Absolute => self.pulse1_absolute().map(f64::from),
+            Field::ExternalTemperature1 => self.external_temperature_1.map(tenths),
+            Field::ExternalTemperature2 => self.external_temperature_2.map(tenths),
+            _ => self.value(field).map(f64::from),
+        }
+    }
+
+    /// The field as a number, in the units of its accessor. Occupancy is 0, 1 or 2 as on the wire,
+    /// and external digital is 0 or 1. Absolute pulse counts above 2^24 are not exact in an `f32`;
+    /// `value_f64` keeps them exact.
+    pub fn value(&self, field: Field) -> Option<f32> {
+        match field {
+            Field::Temperature => self.temperature(),
+            Field::Co2 => self.co2_ppm().map(f32::from),
+            Field::Battery => self.battery_voltage(),
+            Field::Occupancy => self.occupancy().map(|o| match o {
+                Occupancy::NoBody => 0.0,
+                Occupancy::PendingOrPir => 1.0,
+                Occupancy::OccupiedOrHeat => 2.0,
+            }),
+            Field::ExternalDigital => self.external_digital().map(|d| if d { 1.0 } else { 0.0 }),
+            Field::Humidity => self.humidity_percent().map(f32::from),
+            Field::Light => self.light_lux().map(f32::from),
+            Field::Motion => self.motion_count().map(f32::from),
+            Field::Pulse1 => self.pulse1().map(f32::from),
+            Field::Pulse1Absolute => self.pulse1_absolute().map(|p| p as f32),
+            Field::ExternalTemperature1 => self.external_temperature_1(),
+            Field::ExternalTemperature2 => self.external_temperature_2(),
+            Field::Distance => self.distance_mm().map(f32::from),
+            Field::AccelerationMotion => self.acceleration_motion().map(f32::from),
+            Field::Waterleak => self.waterleak().map(f32::from),
+        }
+    }
+}
+
+/// The identifier, size and name of every record in the layout.
+pub(crate) fn records() -> impl Iterator<Item = (u8, usize, &'static str)> {
+    LAYOUT
+        .iter()
+        .map(|layout| (layout.identifier, layout.size, layout.name))
+}
+
+pub(crate) fn record_size(identifier: u8) -> Option<usize> {
+    LAYOUT
+        .iter()
+        .find(|layout| layout.identifier == identifier)
+        .map(|layout| layout.size)
 }
 
-fn verify_array_length(input: &[u8], i: usize, pattern_size: usize) -> Result<()> {
+/// Decodes one complete record, identifier included, into `output`.
+pub(crate) fn decode_record(record: &[u8], output: &mut Uplink) -> Decoded {
+    match LAYOUT
+        .iter()
+        .find(|layout| Some(&layout.identifier) == record.first())
+    {
+        Some(layout) => {
+            verify_array_length(record, 0, layout.size)?;
+            (layout.bin_to)(record, 1, output)
+        }
+        None => verify_pattern_matches(0, false),
+    }
+}
+
+pub(crate) fn verify_array_length(input: &[u8], i: usize, pattern_size: usize) -> Decoded {
     if input.len() <= i + pattern_size {
-        return Err(Error::new(
-            ErrorKind::InvalidData,
-            format!(
-                "{:?} does not look like an Elsys Uplink \
-            (index {} has value {}, which is length {})",
-                input, i, input[i], pattern_size
-            ),
-        ));
+        return Err(Fault {
+            i,
+            reason: Reason::Length(pattern_size),
+        });
     }
 
     Ok(())
 }
 
-fn verify_pattern_matches(input: &[u8], i: usize, identifier_found: bool) -> Result<()> {
+pub(crate) fn verify_pattern_matches(i: usize, identifier_found: bool) -> Decoded {
     if !identifier_found {
-        return Err(Error::new(
-            ErrorKind::InvalidData,
-            format!(
-                "{:?} does not look like an Elsys Uplink \
-            (index {} has value {}, which is not an identifier)",
-                input, i, input[i]
-            ),
-        ));
+        return Err(Fault {
+            i,
+            reason: Reason::NotIdentifier,
+        });
     }
 
     Ok(())
 }
 
-fn temperature(input: &[u8], i: usize, output: &mut Uplink) -> Result<()> {
-    let temperature_x10_pos = ((input[i] as u16) << 8) | input[i + 1] as u16;
+fn temperature(input: &[u8], i: usize, output: &mut Uplink) -> Decoded {
+    let temperature_x10_pos = word(input, i)?;
     let temperature_x10 = bin16_to_dec(temperature_x10_pos);
     output.temperature = Some(temperature_x10 as f32 * 0.1);
     Ok(())
 }
 
-fn co2(input: &[u8], i: usize, output: &mut Uplink) -> Result<()> {
-    output.co2 = Some(((input[i] as u16) << 8) | input[i + 1] as u16);
+fn humidity(input: &[u8], i: usize, output: &mut Uplink) -> Decoded {
+    output.humidity = Some(byte(input, i)?);
+    Ok(())
+}
+
+fn acceleration(input: &[u8], i: usize, output: &mut Uplink) -> Decoded {
+    output.acceleration = Some([
+        byte(input, i)? as i8,
+        byte(input, i + 1)? as i8,
+        byte(input, i + 2)? as i8,
+    ]);
+    Ok(())
+}
+
+/// Latitude and longitude are each sent as 24-bit signed integers, little-endian unlike the other
+/// records.
+fn gps(input: &[u8], i: usize, output: &mut Uplink) -> Decoded {
+    output.gps = Some([signed_24_le(input, i)?, signed_24_le(input, i + 3)?]);
+    Ok(())
+}
+
+fn light(input: &[u8], i: usize, output: &mut Uplink) -> Decoded {
+    output.light = Some(word(input, i)?);
+    Ok(())
+}
+
+fn motion(input: &[u8], i: usize, output: &mut Uplink) -> Decoded {
+    output.motion = Some(byte(input, i)?);
+    Ok(())
+}
+
+fn co2(input: &[u8], i: usize, output: &mut Uplink) -> Decoded {
+    output.co2 = Some(word(input, i)?);
+    Ok(())
+}
+
+fn battery(input: &[u8], i: usize, output: &mut Uplink) -> Decoded {
+    output.battery_mv = Some(word(input, i)?);
+    Ok(())
+}
+
+fn pulse1(input: &[u8], i: usize, output: &mut Uplink) -> Decoded {
+    output.pulse1 = Some(word(input, i)?);
+    Ok(())
+}
+
+fn pulse1_absolute(input: &[u8], i: usize, output: &mut Uplink) -> Decoded {
+    output.pulse1_absolute = Some(double_word(input, i)?);
     Ok(())
 }
 
-fn battery(input: &[u8], i: usize, output: &mut Uplink) -> Result<()> {
-    output.battery_mv = Some(((input[i] as u16) << 8) | input[i + 1] as u16);
+fn external_temperature_1(input: &[u8], i: usize, output: &mut Uplink) -> Decoded {
+    output.external_temperature_1 = Some(bin16_to_dec(word(input, i)?) as f32 * 0.1);
     Ok(())
 }
 
-fn external_digital(input: &[u8], i: usize, output: &mut Uplink) -> Result<()> {
-    output.external_digital = match input[i] {
+fn external_digital(input: &[u8], i: usize, output: &mut Uplink) -> Decoded {
+    output.external_digital = match byte(input, i)? {
         0 => Some(false),
         1 => Some(true),
         _ => {
-            return Err(Error::new(
-                ErrorKind::InvalidData,
-                format!(
-                    "{:?}: index {} has value {}, which is not a window contact value",
-                    input, i, input[i]
-                ),
-            ))
+            return Err(Fault {
+                i,
+                reason: Reason::NotWindowContact,
+            })
         }
     };
     Ok(())
 }
 
-fn occupancy(input: &[u8], i: usize, output: &mut Uplink) -> Result<()> {
-    output.occupancy = match input[i] {
+fn distance(input: &[u8], i: usize, output: &mut Uplink) -> Decoded {
+    output.distance = Some(word(input, i)?);
+    Ok(())
+}
+
+fn acceleration_motion(input: &[u8], i: usize, output: &mut Uplink) -> Decoded {
+    output.acceleration_motion = Some(byte(input, i)?);
+    Ok(())
+}
+
+/// The internal temperature followed by external temperature 1, each encoded as in their own
+/// records.
+fn internal_and_external_temperatures(input: &[u8], i: usize, output: &mut Uplink) -> Decoded {
+    temperature(input, i, output)?;
+    external_temperature_1(input, i + 2, output)
+}
+
+fn occupancy(input: &[u8], i: usize, output: &mut Uplink) -> Decoded {
+    output.occupancy = match byte(input, i)? {
         0 => Some(Occupancy::NoBody),
         1 => Some(Occupancy::PendingOrPir),
         2 => Some(Occupancy::OccupiedOrHeat),
         _ => {
-            return Err(Error::new(
-                ErrorKind::InvalidData,
-                format!(
-                    "{:?}: index {} has value {}, which is not an occupancy value",
-                    input, i, input[i]
-                ),
-            ))
+            return Err(Fault {
+                i,
+                reason: Reason::NotOccupancy,
+            })
         }
     };
     Ok(())
 }
 
-fn no_decode(_: &[u8], _: usize, _: &mut Uplink) -> Result<()> {
+fn waterleak(input: &[u8], i: usize, output: &mut Uplink) -> Decoded {
+    output.waterleak = Some(byte(input, i)?);
+    Ok(())
+}
+
+fn external_temperature_2(input: &[u8], i: usize, output: &mut Uplink) -> Decoded {
+    output.external_temperature_2 = Some(bin16_to_dec(word(input, i)?) as f32 * 0.1);
     Ok(())
 }
 
+fn no_decode(input: &[u8], i: usize, output: &mut Uplink) -> Decoded {
+    // `i` is never 0 here, and would be out of range if it were.
+    let index = i.wrapping_sub(1);
+    let identifier = byte(input, index)?;
+
+    #[cfg(feature = "tracing")]
+    tracing::warn!(
+        index,
+        identifier,
+        "identifier is recognised but not decoded"
+    );
+
+    if !output.undecoded.contains(&identifier) {
+        output.undecoded.push(identifier);
+    }
+    Ok(())
+}
+
+pub(crate) type Decoded<T = ()> = std::result::Result<T, Fault>;
+
+/// Why a payload failed to decode, at index `i`. The payload is only quoted once the fault becomes
+/// an error, so that decoders need not know the `ErrorDetail`.
+#[derive(Debug)]
+pub(crate) struct Fault {
+    i: usize,
+    reason: Reason,
+}
+
+#[derive(Debug)]
+enum Reason {
+    OutOfRange,
+    /// The identifier at `i` is for a record of this length, which the payload is too short for.
+    Length(usize),
+    NotIdentifier,
+    NotWindowContact,
+    NotOccupancy,
+}
+
+impl Fault {
+    pub(crate) fn into_error(self, input: &[u8], detail: ErrorDetail) -> Error {
+        let i = self.i;
+        let excerpt = Excerpt { input, i, detail };
+        let value = input.get(i).copied().unwrap_or_default();
+        let redacted = matches!(detail, ErrorDetail::Redacted | ErrorDetail::Bytes(0));
+        let message = match self.reason {
+            Reason::OutOfRange => format!("{}: index {} is out of range", excerpt, i),
+            Reason::Length(size) => format!(
+                "{} does not look like an Elsys Uplink \
+                (index {} has value {}, which is length {})",
+                excerpt, i, value, size
+            ),
+            Reason::NotIdentifier => format!(
+                "{} does not look like an Elsys Uplink \
+                (index {} has value {}, which is not an identifier)",
+                excerpt, i, value
+            ),
+            Reason::NotWindowContact | Reason::NotOccupancy => {
+                let what = match self.reason {
+                    Reason::NotWindowContact => "a window contact value",
+                    _ => "an occupancy value",
+                };
+                if redacted {
+                    format!("{}: index {} is not {}", excerpt, i, what)
+                } else {
+                    format!(
+                        "{}: index {} has value {}, which is not {}",
+                        excerpt, i, value, what
+                    )
+                }
+            }
+        };
+        Error::new(ErrorKind::InvalidData, message)
+    }
+}
+
+/// A payload as quoted in errors, bounded by the `ErrorDetail`.
+pub(crate) struct Excerpt<'a> {
+    input: &'a [u8],
+    i: usize,
+    detail: ErrorDetail,
+}
+
+impl fmt::Display for Excerpt<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let length = self.input.len();
+        match self.detail {
+            ErrorDetail::Bytes(bytes) if length <= bytes => write!(f, "{:?}", self.input),
+            ErrorDetail::Bytes(bytes) if bytes > 0 => {
+                let start = self.i.min(length - bytes);
+                let shown = self.input.get(start..start + bytes).unwrap_or_default();
+                write!(
+                    f,
+                    "{}-byte payload, bytes {}..{} {:?}",
+                    length,
+                    start,
+                    start + bytes,
+                    shown
+                )
+            }
+            _ => write!(f, "{}-byte payload", length),
+        }
+    }
+}
+
+fn byte(input: &[u8], i: usize) -> Decoded<u8> {
+    input.get(i).copied().ok_or(Fault {
+        i,
+        reason: Reason::OutOfRange,
+    })
+}
+
+fn word(input: &[u8], i: usize) -> Decoded<u16> {
+    Ok(((byte(input, i)? as u16) << 8) | byte(input, i + 1)? as u16)
+}
+
+fn double_word(input: &[u8], i: usize) -> Decoded<u32> {
+    Ok((word(input, i)? as u32) << 16 | word(input, i + 2)? as u32)
+}
+
+fn signed_24_le(input: &[u8], i: usize) -> Decoded<i32> {
+    let unsigned = byte(input, i)? as i32
+        | (byte(input, i + 1)? as i32) << 8
+        | (byte(input, i + 2)? as i32) << 16;
+    Ok((unsigned << 8) >> 8)
+}
+
 fn bin16_to_dec(bin: u16) -> i16 {
     if 0x8000 & bin == 0 {
         bin as i16
@@ -216,5 +810,6 @@ fn bin16_to_dec(bin: u16) -> i16 {
 
 #[rustfmt::skip]
 #[cfg(test)]
+#[allow(clippy::indexing_slicing, clippy::unwrap_used)]
 #[path = "./test_uplink.rs"]
 mod test_uplink;
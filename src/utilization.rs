@@ -0,0 +1,123 @@
+use crate::calendar::Calendar;
+use crate::{Occupancy, Uplink};
+
+pub const HOUR_S: u64 = 60 * 60;
+pub const DAY_S: u64 = 24 * HOUR_S;
+
+/// Occupancy over one period, starting at `start_s` (seconds since the Unix epoch).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Utilization {
+    pub start_s: u64,
+    pub occupied_s: u64,
+    pub observed_s: u64,
+}
+
+impl Utilization {
+    pub fn occupied_minutes(&self) -> u64 {
+        self.occupied_s / 60
+    }
+
+    pub fn ratio(&self) -> Option<f32> {
+        if self.observed_s == 0 {
+            None
+        } else {
+            Some(self.occupied_s as f32 / self.observed_s as f32)
+        }
+    }
+}
+
+pub fn per_hour(readings: &[(u64, Uplink)]) -> Vec<Utilization> {
+    per_period(readings, HOUR_S)
+}
+
+pub fn per_day(readings: &[(u64, Uplink)]) -> Vec<Utilization> {
+    per_period(readings, DAY_S)
+}
+
+/// Splits a device's occupancy history into periods of `period_s` seconds, aligned to the Unix
+/// epoch.
+///
+/// `readings` must be sorted by timestamp. Each occupancy value holds until the next reading which
+/// carries an occupancy value; only `Occupancy::OccupiedOrHeat` counts as occupied. A `period_s` of
+/// zero yields no periods.
+pub fn per_period(readings: &[(u64, Uplink)], period_s: u64) -> Vec<Utilization> {
+    per_period_counting(readings, period_s, |from_s, to_s| to_s - from_s)
+}
+
+/// As `per_period`, counting only the time the calendar has the building open. Periods entirely
+/// outside it are omitted.
+pub fn per_period_within(
+    readings: &[(u64, Uplink)],
+    period_s: u64,
+    calendar: &Calendar,
+) -> Vec<Utilization> {
+    per_period_counting(readings, period_s, |from_s, to_s| {
+        calendar.open_s(from_s, to_s)
+    })
+}
+
+fn per_period_counting<F: Fn(u64, u64) -> u64>(
+    readings: &[(u64, Uplink)],
+    period_s: u64,
+    counted_s: F,
+) -> Vec<Utilization> {
+    let mut output: Vec<Utilization> = Vec::new();
+    if period_s == 0 {
+        return output;
+    }
+
+    let states = readings
+        .iter()
+        .filter_map(|(timestamp_s, uplink)| uplink.occupancy().map(|o| (*timestamp_s, o)))
+        .collect::<Vec<_>>();
+
+    for pair in states.windows(2) {
+        let (mut from_s, occupancy) = pair[0];
+        let (to_s, _) = pair[1];
+
+        while from_s < to_s {
+            let start_s = from_s - from_s % period_s;
+            let until_s = to_s.min(start_s + period_s);
+            let duration_s = counted_s(from_s, until_s);
+            if duration_s == 0 {
+                from_s = until_s;
+                continue;
+            }
+
+            if output.last().map(|last| last.start_s) != Some(start_s) {
+                output.push(Utilization {
+                    start_s,
+                    occupied_s: 0,
+                    observed_s: 0,
+                });
+            }
+            if let Some(bucket) = output.last_mut() {
+                bucket.observed_s += duration_s;
+                if occupancy == Occupancy::OccupiedOrHeat {
+                    bucket.occupied_s += duration_s;
+                }
+            }
+
+            from_s = until_s;
+        }
+    }
+
+    output
+}
+
+/// The `count` most occupied periods, busiest first.
+pub fn peaks(periods: &[Utilization], count: usize) -> Vec<Utilization> {
+    let mut sorted = periods.to_vec();
+    sorted.sort_by(|a, b| {
+        b.occupied_s
+            .cmp(&a.occupied_s)
+            .then(a.start_s.cmp(&b.start_s))
+    });
+    sorted.truncate(count);
+    sorted
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[path = "./test_utilization.rs"]
+mod test_utilization;
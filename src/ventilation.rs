@@ -0,0 +1,99 @@
+use crate::aggregate::quantile;
+use crate::trend::fit;
+use crate::{Occupancy, Uplink};
+
+/// How CO2 decay periods are chosen and fitted.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DecayConfig {
+    pub outdoor_ppm: f32,
+    /// Shortest vacant period to fit.
+    pub min_duration_s: u64,
+    /// Readings closer to outdoor CO2 than this are left out, as noise dominates them.
+    pub min_excess_ppm: f32,
+}
+
+impl Default for DecayConfig {
+    fn default() -> Self {
+        Self {
+            outdoor_ppm: 420.0,
+            min_duration_s: 1800,
+            min_excess_ppm: 100.0,
+        }
+    }
+}
+
+/// The air change rate fitted to one vacant period.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Decay {
+    pub start_s: u64,
+    pub end_s: u64,
+    pub air_changes_per_hour: f32,
+}
+
+/// Fits `C(t) = outdoor + (C0 - outdoor) * exp(-ACH * t)` to the CO2 of each period the room is
+/// reported vacant, from the first `Occupancy::NoBody` until any other occupancy. Readings must be
+/// in chronological order.
+pub fn decays(readings: &[(u64, Uplink)], config: &DecayConfig) -> Vec<Decay> {
+    let mut output = Vec::new();
+    let mut period: Vec<(u64, f32)> = Vec::new();
+    let mut start_s = None;
+
+    for (timestamp_s, uplink) in readings {
+        match uplink.occupancy() {
+            Some(Occupancy::NoBody) => {
+                start_s.get_or_insert(*timestamp_s);
+            }
+            Some(_) => {
+                if let Some(decay) = fit_period(start_s.take(), &period, config) {
+                    output.push(decay);
+                }
+                period.clear();
+            }
+            None => {}
+        }
+
+        if let (Some(_), Some(co2)) = (start_s, uplink.co2_ppm()) {
+            period.push((*timestamp_s, co2 as f32));
+        }
+    }
+    output.extend(fit_period(start_s, &period, config));
+
+    output
+}
+
+/// The median air change rate of the decays.
+pub fn air_changes_per_hour(decays: &[Decay]) -> Option<f32> {
+    let rates = decays
+        .iter()
+        .map(|decay| decay.air_changes_per_hour)
+        .collect::<Vec<_>>();
+    quantile(&rates, 0.5)
+}
+
+fn fit_period(start_s: Option<u64>, period: &[(u64, f32)], config: &DecayConfig) -> Option<Decay> {
+    let start_s = start_s?;
+    let end_s = period.last()?.0;
+    if end_s.saturating_sub(start_s) < config.min_duration_s {
+        return None;
+    }
+
+    let points = period
+        .iter()
+        .filter(|(_, co2)| co2 - config.outdoor_ppm >= config.min_excess_ppm)
+        .map(|(timestamp_s, co2)| (*timestamp_s, (co2 - config.outdoor_ppm).ln()));
+    if points.clone().count() < 3 {
+        return None;
+    }
+
+    let air_changes_per_hour = -fit(points)?;
+    (air_changes_per_hour > 0.0).then_some(Decay {
+        start_s,
+        end_s,
+        air_changes_per_hour,
+    })
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+#[path = "./test_ventilation.rs"]
+mod test_ventilation;